@@ -0,0 +1,29 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("part one, " "part two")]
+    Unit,
+
+    #[error(
+        "Expected {expected} items, "
+        "found {found} "
+        "({})",
+        found - expected,
+    )]
+    Struct { expected: usize, found: usize },
+
+    #[error("{0}" " and " "{1}")]
+    Tuple(u8, u8),
+}
+
+fn main() {
+    assert_eq!(Error::Unit.to_string(), "part one, part two");
+    assert_eq!(
+        Error::Struct {
+            expected: 2,
+            found: 5,
+        }
+        .to_string(),
+        "Expected 2 items, found 5 (3)",
+    );
+    assert_eq!(Error::Tuple(1, 2).to_string(), "1 and 2");
+}
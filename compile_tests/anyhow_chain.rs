@@ -0,0 +1,32 @@
+use std::io::{Error as IoError, ErrorKind};
+
+#[derive(Debug, onlyerror::Error)]
+enum LoadError {
+    /// Unable to read file
+    Io(#[from] IoError),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum AppError {
+    #[error("Config `{path}` failed")]
+    Config {
+        path: String,
+        #[source]
+        source: LoadError,
+    },
+}
+
+fn main() {
+    let err = AppError::Config {
+        path: "app.toml".into(),
+        source: LoadError::from(IoError::new(ErrorKind::NotFound, "not found")),
+    };
+    let err = anyhow::Error::from(err);
+
+    assert_eq!(format!("{err}"), "Config `app.toml` failed");
+    assert_eq!(
+        format!("{err:#}"),
+        "Config `app.toml` failed: Unable to read file: not found",
+    );
+    assert_eq!(err.chain().count(), 3);
+}
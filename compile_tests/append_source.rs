@@ -0,0 +1,90 @@
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+#[error(append_source)]
+enum Error {
+    #[error("failed to load {path}")]
+    Load {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// Invalid number
+    Number(#[from] std::num::ParseIntError),
+
+    #[error("{}", "boxed")]
+    Boxed(#[source] Box<std::fmt::Error>),
+
+    #[error("already displayed: {inner}")]
+    Displayed {
+        #[source]
+        inner: std::fmt::Error,
+    },
+
+    #[error(concat!("from ", "a macro"))]
+    Macro(#[source] std::fmt::Error),
+
+    #[error("no source")]
+    Plain,
+
+    #[error("maybe failed")]
+    Optional {
+        #[source]
+        cause: Option<std::fmt::Error>,
+    },
+
+    #[error("maybe boxed")]
+    OptionalBoxed(#[source] Option<Box<dyn std::error::Error + Send + Sync>>),
+}
+
+fn main() {
+    let err = Error::Load {
+        path: "a.toml".to_string(),
+        source: std::io::Error::new(std::io::ErrorKind::NotFound, "not found"),
+    };
+    assert_eq!(err.to_string(), "failed to load a.toml: not found");
+    assert!(err.source().is_some());
+
+    let err = Error::from("x".parse::<u8>().unwrap_err());
+    assert_eq!(
+        err.to_string(),
+        "Invalid number: invalid digit found in string",
+    );
+
+    let err = Error::Boxed(Box::new(std::fmt::Error));
+    assert_eq!(
+        err.to_string(),
+        "boxed: an error occurred when formatting an argument",
+    );
+
+    let err = Error::Displayed {
+        inner: std::fmt::Error,
+    };
+    assert_eq!(
+        err.to_string(),
+        "already displayed: an error occurred when formatting an argument",
+    );
+
+    let err = Error::Macro(std::fmt::Error);
+    assert_eq!(
+        err.to_string(),
+        "from a macro: an error occurred when formatting an argument",
+    );
+
+    assert_eq!(Error::Plain.to_string(), "no source");
+
+    let err = Error::Optional {
+        cause: Some(std::fmt::Error),
+    };
+    assert_eq!(
+        err.to_string(),
+        "maybe failed: an error occurred when formatting an argument",
+    );
+    assert_eq!(Error::Optional { cause: None }.to_string(), "maybe failed");
+
+    let err = Error::OptionalBoxed(Some("inner".into()));
+    assert_eq!(err.to_string(), "maybe boxed: inner");
+    assert!(err.source().is_some());
+    assert_eq!(Error::OptionalBoxed(None).to_string(), "maybe boxed");
+}
@@ -0,0 +1,40 @@
+use std::error::Error as _;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, onlyerror::Error)]
+enum Error {
+    /// Shared error
+    Shared(#[from] Arc<dyn std::error::Error + Send + Sync>),
+
+    #[error("Retry {attempt} failed")]
+    Retry {
+        attempt: u32,
+        #[source]
+        source: std::sync::Arc<std::io::Error>,
+    },
+}
+
+fn main() {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "disk full");
+    let shared: Arc<dyn std::error::Error + Send + Sync> = Arc::new(io);
+    let err = Error::from(Arc::clone(&shared));
+    let cloned = err.clone();
+    assert_eq!(Arc::strong_count(&shared), 3);
+
+    let source = cloned.source().unwrap();
+    assert_eq!(source.to_string(), "disk full");
+    assert!(source.downcast_ref::<std::io::Error>().is_some());
+
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "timed out");
+    let err = Error::Retry {
+        attempt: 2,
+        source: Arc::new(io),
+    };
+    let cloned = err.clone();
+    assert_eq!(cloned.to_string(), "Retry 2 failed");
+    assert!(cloned
+        .source()
+        .unwrap()
+        .downcast_ref::<std::io::Error>()
+        .is_some());
+}
@@ -0,0 +1,69 @@
+use std::error::Error as _;
+use std::io::{Error as IoError, ErrorKind};
+
+#[derive(Debug, onlyerror::Error)]
+enum Before {
+    /// Documentation
+    #[error("I/O failed: {0}")]
+    Io(
+        /// The source
+        #[from]
+        IoError,
+    ),
+
+    #[allow(dead_code)]
+    #[error("Parse failed in {path}")]
+    Parse {
+        #[source]
+        /// The source
+        source: std::num::ParseIntError,
+        path: String,
+    },
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum After {
+    #[error("I/O failed: {0}")]
+    /// Documentation
+    Io(
+        #[from]
+        /// The source
+        IoError,
+    ),
+
+    #[error("Parse failed in {path}")]
+    #[allow(dead_code)]
+    Parse {
+        /// The source
+        #[source]
+        source: std::num::ParseIntError,
+        path: String,
+    },
+}
+
+fn main() {
+    let before = Before::from(IoError::new(ErrorKind::Other, "io"));
+    let after = After::from(IoError::new(ErrorKind::Other, "io"));
+    assert_eq!(before.to_string(), "I/O failed: io");
+    assert_eq!(before.to_string(), after.to_string());
+    assert_eq!(
+        before.source().unwrap().to_string(),
+        after.source().unwrap().to_string(),
+    );
+
+    let source = "x".parse::<u8>().unwrap_err();
+    let before = Before::Parse {
+        source: source.clone(),
+        path: "a.txt".into(),
+    };
+    let after = After::Parse {
+        source,
+        path: "a.txt".into(),
+    };
+    assert_eq!(before.to_string(), "Parse failed in a.txt");
+    assert_eq!(before.to_string(), after.to_string());
+    assert_eq!(
+        before.source().unwrap().to_string(),
+        after.source().unwrap().to_string(),
+    );
+}
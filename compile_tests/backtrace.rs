@@ -0,0 +1,29 @@
+use std::backtrace::Backtrace;
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    /// I/O error
+    Io(#[from] std::io::Error, #[backtrace] Backtrace),
+
+    /// Parse error
+    Parse {
+        #[backtrace]
+        backtrace: Backtrace,
+        #[from]
+        source: std::num::ParseIntError,
+    },
+}
+
+fn main() {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "oops");
+    let err = Error::from(io);
+    assert!(err.source().unwrap().is::<std::io::Error>());
+    assert!(matches!(err, Error::Io(_, _)));
+
+    let err = Error::from("x".parse::<u8>().unwrap_err());
+    let Error::Parse { backtrace, .. } = &err else {
+        panic!("expected a parse error");
+    };
+    let _ = backtrace.status();
+}
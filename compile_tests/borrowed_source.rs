@@ -0,0 +1,17 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error<'a> {
+    /// Invalid number
+    Number(#[source] &'a std::num::ParseIntError),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Struct<'a> {
+    #[error("Invalid input {input}")]
+    Input {
+        input: &'a str,
+        #[from]
+        source: &'a std::fmt::Error,
+    },
+}
+
+fn main() {}
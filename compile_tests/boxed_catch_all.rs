@@ -0,0 +1,27 @@
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    // Type aliases are not recognized as boxes, so the deref has to be requested.
+    #[error("Other error: {0}")]
+    Other(#[from(deref)] BoxError),
+}
+
+fn parse(input: &str) -> Result<u8, Error> {
+    Ok(input.parse::<u8>().map_err(Box::from)?)
+}
+
+fn utf8(input: &[u8]) -> Result<&str, Error> {
+    Ok(std::str::from_utf8(input).map_err(BoxError::from)?)
+}
+
+fn main() {
+    let err = parse("x").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Other error: invalid digit found in string"
+    );
+
+    let bytes = vec![0xff];
+    assert!(matches!(utf8(&bytes), Err(Error::Other(_))));
+}
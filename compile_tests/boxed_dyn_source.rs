@@ -0,0 +1,29 @@
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    /// Plugin error
+    Plugin(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+    #[error("Plugin {name} failed")]
+    Named {
+        name: String,
+        #[source]
+        source: std::boxed::Box<dyn std::error::Error + 'static>,
+    },
+}
+
+fn main() {
+    let parse = "x".parse::<u8>().unwrap_err();
+    let err = Error::Plugin(Box::new(parse));
+    let source = err.source().unwrap();
+    assert!(source.is::<std::num::ParseIntError>());
+
+    let parse = "x".parse::<u8>().unwrap_err();
+    let err = Error::Named {
+        name: "fmt".to_string(),
+        source: Box::new(parse),
+    };
+    assert_eq!(err.to_string(), "Plugin fmt failed");
+    assert!(err.source().unwrap().is::<std::num::ParseIntError>());
+}
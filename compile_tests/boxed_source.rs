@@ -0,0 +1,21 @@
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("Leaf: {0}")]
+    Leaf(String),
+
+    /// Nested error
+    Nested(#[from] Box<Error>),
+}
+
+fn main() {
+    let leaf = Box::new(Error::Leaf("oops".into()));
+    let err = Error::from(leaf);
+    assert_eq!(err.to_string(), "Nested error");
+
+    let source = err.source().unwrap();
+    assert_eq!(source.to_string(), "Leaf: oops");
+    assert!(matches!(source.downcast_ref(), Some(Error::Leaf(_))));
+    assert!(source.source().is_none());
+}
@@ -0,0 +1,15 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    /// Parse error
+    Parse(#[from] std::num::ParseIntError),
+
+    /// Other error
+    #[error(catch_all)]
+    Other(Box<dyn std::error::Error + Send + Sync>),
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error("Application error")]
+struct AppError(#[from(any)] Box<dyn std::error::Error + Send + Sync>);
+
+fn main() {}
@@ -0,0 +1,43 @@
+//! `cfg` and `cfg_attr` are expanded before the derive runs, so gated variants, fields, and
+//! attributes stay in lockstep with the generated impls.
+
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    /// Enabled
+    #[cfg(all())]
+    Enabled(#[from] std::io::Error),
+
+    /// Disabled
+    #[cfg(any())]
+    Disabled(#[from] std::fmt::Error),
+
+    #[cfg_attr(all(), error("Gated message {code}"))]
+    Message {
+        code: u8,
+        #[cfg(any())]
+        extra: String,
+    },
+
+    #[error("Context {0}")]
+    Context(
+        #[cfg(all())] u8,
+        #[cfg_attr(all(), source)]
+        #[cfg(all())]
+        std::num::ParseIntError,
+    ),
+}
+
+fn main() {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "oops");
+    let err = Error::from(io);
+    assert!(err.source().unwrap().is::<std::io::Error>());
+
+    assert_eq!(Error::Message { code: 1 }.to_string(), "Gated message 1");
+
+    let parse = "x".parse::<u8>().unwrap_err();
+    let err = Error::Context(2, parse);
+    assert_eq!(err.to_string(), "Context 2");
+    assert!(err.source().unwrap().is::<std::num::ParseIntError>());
+}
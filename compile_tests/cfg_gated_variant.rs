@@ -0,0 +1,38 @@
+//! `cfg` gates on variants are honored: `all()` is always enabled and `any()` is always
+//! disabled, standing in for `feature = "std"` with and without the feature.
+
+#![no_std]
+
+extern crate std;
+
+use core::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+enum WithStd {
+    /// Invalid digit
+    Parse(#[from] core::num::ParseIntError),
+
+    /// I/O error
+    #[cfg(all())]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum WithoutStd {
+    /// Invalid digit
+    Parse(#[from] core::num::ParseIntError),
+
+    /// I/O error
+    #[cfg(any())]
+    Io(#[from] std::io::Error),
+}
+
+fn main() {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "oops");
+    let err = WithStd::from(io);
+    assert!(err.source().unwrap().is::<std::io::Error>());
+
+    let parse = "x".parse::<u8>().unwrap_err();
+    let err = WithoutStd::from(parse);
+    assert!(err.source().unwrap().is::<core::num::ParseIntError>());
+}
@@ -0,0 +1,28 @@
+#[derive(Debug, onlyerror::Error)]
+#[error(chain)]
+enum Error {
+    /// Config error
+    Config(#[from] ConfigError),
+
+    /// Unknown
+    Unknown,
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error("Config file is invalid")]
+struct ConfigError {
+    #[source]
+    source: std::io::Error,
+}
+
+fn main() {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "disk full");
+    let err = Error::from(ConfigError { source: io });
+    let messages = err.chain().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        ["Config error", "Config file is invalid", "disk full"]
+    );
+
+    assert_eq!(Error::Unknown.chain().count(), 1);
+}
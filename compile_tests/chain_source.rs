@@ -0,0 +1,43 @@
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+#[error(chain_source)]
+enum Error {
+    Io(#[from] std::io::Error),
+
+    Parse {
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+        line: usize,
+    },
+
+    /// Has its own message
+    Other(#[source] std::fmt::Error),
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error(chain_source, prefix = "app: ")]
+enum Prefixed {
+    Io(#[from] std::io::Error),
+}
+
+fn main() {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "disk full");
+    let err = Error::from(io);
+    assert_eq!(err.to_string(), "disk full");
+    assert_eq!(err.source().unwrap().to_string(), "disk full");
+
+    let err = Error::Parse {
+        source: "bad digit".into(),
+        line: 1,
+    };
+    assert_eq!(err.to_string(), "bad digit");
+
+    assert_eq!(
+        Error::Other(std::fmt::Error).to_string(),
+        "Has its own message"
+    );
+
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "disk full");
+    assert_eq!(Prefixed::from(io).to_string(), "app: disk full");
+}
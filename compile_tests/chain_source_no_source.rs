@@ -0,0 +1,9 @@
+#[derive(Debug, onlyerror::Error)]
+#[error(chain_source)]
+enum Error {
+    Io(#[from] std::io::Error),
+
+    Unknown,
+}
+
+fn main() {}
@@ -13,4 +13,131 @@ fn compile_tests() {
         t.compile_fail("compile_tests/no_display_no_impl.rs");
     }
     t.pass("compile_tests/skip_positional.rs");
+    t.pass("compile_tests/explicit_args.rs");
+    t.pass("compile_tests/anyhow_chain.rs");
+    t.pass("compile_tests/boxed_source.rs");
+    t.pass("compile_tests/empty_message.rs");
+    t.pass("compile_tests/self_source.rs");
+    t.pass("compile_tests/tuple_source_index.rs");
+    t.pass("compile_tests/from_custom_message.rs");
+    t.compile_fail("compile_tests/from_multiple_fields.rs");
+    t.pass("compile_tests/from_default_fields.rs");
+    t.pass("compile_tests/no_prelude.rs");
+    t.pass("compile_tests/formatter_shadowing.rs");
+    t.pass("compile_tests/tuple_alias.rs");
+    t.pass("compile_tests/display_delegate.rs");
+    t.pass("compile_tests/nested_module.rs");
+    t.pass("compile_tests/explicit_args_fields.rs");
+    t.pass("compile_tests/field_paths.rs");
+    t.pass("compile_tests/const_generic_array.rs");
+    t.compile_fail("compile_tests/conflicting_from.rs");
+    t.pass("compile_tests/placeholder_punctuation.rs");
+    if rustversion::cfg!(since(1.81.0)) {
+        t.pass("compile_tests/no_std_alloc.rs");
+    }
+    if cfg!(all(feature = "alloc", not(feature = "std"))) && rustversion::cfg!(since(1.81.0)) {
+        t.pass("compile_tests/no_std_alloc_only.rs");
+    }
+    t.pass("compile_tests/attribute_order.rs");
+    t.pass("compile_tests/missing_docs.rs");
+    t.pass("compile_tests/inline_and_named_args.rs");
+    t.pass("compile_tests/deprecated_variant.rs");
+    t.pass("compile_tests/multiline_docs.rs");
+    t.pass("compile_tests/arc_source.rs");
+    t.pass("compile_tests/escaped_placeholder.rs");
+    t.compile_fail("compile_tests/malformed_index.rs");
+    t.pass("compile_tests/type_alias.rs");
+    t.pass("compile_tests/tuple_source_alias.rs");
+    t.compile_fail("compile_tests/tuple_type_source.rs");
+    t.pass("compile_tests/doc_trailing_blank.rs");
+    t.pass("compile_tests/doc_whitespace.rs");
+    if rustversion::cfg!(since(1.81.0)) {
+        t.pass("compile_tests/no_std_source_signature.rs");
+    }
+    t.compile_fail("compile_tests/macro_message.rs");
+    if rustversion::cfg!(since(1.81.0)) {
+        t.pass("compile_tests/cfg_gated_variant.rs");
+    }
+    t.pass("compile_tests/cfg_gated_fields.rs");
+    t.pass("compile_tests/doc_and_message.rs");
+    t.pass("compile_tests/unicode_identifiers.rs");
+    t.pass("compile_tests/deref_source.rs");
+    t.compile_fail("compile_tests/deref_source_unknown_option.rs");
+    t.pass("compile_tests/displayed_source.rs");
+    t.pass("compile_tests/adjacent_literals.rs");
+    t.compile_fail("compile_tests/catch_all.rs");
+    t.pass("compile_tests/boxed_catch_all.rs");
+    t.pass("compile_tests/transparent.rs");
+    t.compile_fail("compile_tests/transparent_invalid.rs");
+    t.pass("compile_tests/structs.rs");
+    t.compile_fail("compile_tests/struct_no_message.rs");
+    if cfg!(all(feature = "backtrace", not(feature = "provide"))) {
+        t.pass("compile_tests/backtrace.rs");
+    }
+    if cfg!(all(feature = "backtrace", feature = "provide")) && rustversion::cfg!(nightly) {
+        t.pass("compile_tests/provide.rs");
+    }
+    if cfg!(not(feature = "provide")) {
+        t.compile_fail("compile_tests/provide_disabled.rs");
+    }
+    t.pass("compile_tests/generic_params.rs");
+    t.pass("compile_tests/source_from.rs");
+    t.compile_fail("compile_tests/source_from_no_default.rs");
+    t.pass("compile_tests/prefix.rs");
+    t.pass("compile_tests/predicates.rs");
+    t.compile_fail("compile_tests/predicates_conflict.rs");
+    t.pass("compile_tests/raw_identifiers.rs");
+    t.pass("compile_tests/const_new.rs");
+    t.pass("compile_tests/dynamic_width.rs");
+    t.pass("compile_tests/skip_display.rs");
+    t.compile_fail("compile_tests/skip_display_no_display.rs");
+    t.pass("compile_tests/chain_source.rs");
+    t.compile_fail("compile_tests/chain_source_no_source.rs");
+    t.pass("compile_tests/eq.rs");
+    t.pass("compile_tests/struct_positional.rs");
+    t.pass("compile_tests/error_code.rs");
+    t.compile_fail("compile_tests/error_code_invalid.rs");
+    t.pass("compile_tests/boxed_dyn_source.rs");
+    t.pass("compile_tests/from_generic.rs");
+    t.pass("compile_tests/chain.rs");
+    t.compile_fail("compile_tests/unknown_field.rs");
+    t.pass("compile_tests/default_message.rs");
+    t.compile_fail("compile_tests/default_message_invalid.rs");
+    t.compile_fail("compile_tests/tuple_alias_range.rs");
+    t.pass("compile_tests/from_display_source.rs");
+    if cfg!(feature = "std") {
+        t.pass("compile_tests/from_boxed.rs");
+    }
+    if cfg!(feature = "alloc") {
+        t.compile_fail("compile_tests/from_boxed_invalid.rs");
+    }
+    t.pass("compile_tests/concat_message.rs");
+    if rustversion::cfg!(all(stable, since(1.80.0))) {
+        t.compile_fail("compile_tests/must_use.rs");
+    }
+    t.pass("compile_tests/append_source.rs");
+    t.pass("compile_tests/empty_bodies.rs");
+    t.pass("compile_tests/no_trailing_comma.rs");
+    t.pass("compile_tests/debug_as_display.rs");
+    t.compile_fail("compile_tests/debug_as_display_derive.rs");
+    t.pass("compile_tests/lifetimes.rs");
+    t.compile_fail("compile_tests/borrowed_source.rs");
+    t.pass("compile_tests/kind.rs");
+    t.compile_fail("compile_tests/kind_empty.rs");
+    t.pass("compile_tests/optional_source.rs");
+    t.compile_fail("compile_tests/optional_source_from.rs");
+    t.pass("compile_tests/fmt_fn.rs");
+    t.compile_fail("compile_tests/fmt_fn_invalid.rs");
+    t.pass("compile_tests/from_with.rs");
+    t.compile_fail("compile_tests/from_with_invalid.rs");
+    t.pass("compile_tests/constant_message.rs");
+    t.compile_fail("compile_tests/constant_message_invalid.rs");
+    t.pass("compile_tests/struct_pub_fields.rs");
+    t.pass("compile_tests/unicode_message.rs");
+    t.compile_fail("compile_tests/kind_rename_invalid.rs");
+    t.compile_fail("compile_tests/kind_rename_conflict.rs");
+    t.pass("compile_tests/no_error_impl.rs");
+    t.pass("compile_tests/named_args.rs");
+    t.compile_fail("compile_tests/named_args_duplicate.rs");
+    t.compile_fail("compile_tests/duplicate_field.rs");
 }
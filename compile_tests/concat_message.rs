@@ -0,0 +1,29 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error(concat!("not ", "found"))]
+    NotFound,
+
+    #[error(concat!("invalid header ", "{:?}"), .name)]
+    Header { name: String },
+
+    #[error(include_str!("concat_message.txt"), .0)]
+    Timeout(u64),
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error(prefix = "{app}: ")]
+enum Prefixed {
+    #[error(concat!("not ", "found"))]
+    NotFound,
+}
+
+fn main() {
+    assert_eq!(Error::NotFound.to_string(), "not found");
+    let err = Error::Header {
+        name: "Accept".to_string(),
+    };
+    assert_eq!(err.to_string(), r#"invalid header "Accept""#);
+    assert_eq!(Error::Timeout(30).to_string(), "Timed out after 30 seconds");
+
+    assert_eq!(Prefixed::NotFound.to_string(), "{app}: not found");
+}
@@ -0,0 +1,26 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    /// Read error
+    Read(#[from] std::io::Error),
+
+    /// Write error
+    Write(#[from] std::io::Error),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Struct {
+    /// Parse error
+    Parse {
+        #[from]
+        source: std::num::ParseIntError,
+    },
+
+    #[error("Invalid digit in {input}")]
+    Digit {
+        #[source(from)]
+        source: std::num::ParseIntError,
+        input: String,
+    },
+}
+
+fn main() {}
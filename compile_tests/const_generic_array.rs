@@ -0,0 +1,33 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error<const N: usize> {
+    #[error("Payload is not valid")]
+    Payload([u8; N]),
+
+    #[error("Expected {expected} bytes, found {found}")]
+    Length {
+        expected: usize,
+        found: usize,
+        buffer: [u8; N],
+    },
+
+    /// Buffer overflow
+    Overflow(#[from] std::array::TryFromSliceError),
+}
+
+fn main() {
+    use std::error::Error as _;
+
+    let err = Error::Payload([0; 4]);
+    assert_eq!(err.to_string(), "Payload is not valid");
+
+    let err = Error::<8>::Length {
+        expected: 8,
+        found: 3,
+        buffer: [0; 8],
+    };
+    assert_eq!(err.to_string(), "Expected 8 bytes, found 3");
+
+    let slice_err = <[u8; 2]>::try_from(&[1, 2, 3][..]).unwrap_err();
+    let err = Error::<2>::from(slice_err);
+    assert!(err.source().is_some());
+}
@@ -0,0 +1,49 @@
+#![deny(missing_docs)]
+//! Const constructors are documented.
+
+/// Error codes.
+#[derive(Debug)]
+pub struct Code(u8);
+
+impl std::fmt::Display for Code {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "code {}", self.0)
+    }
+}
+
+impl std::error::Error for Code {}
+
+/// Errors.
+#[derive(Debug, onlyerror::Error)]
+#[error(const_new)]
+pub enum Error<const N: usize> {
+    /// Device error
+    Device(#[from] Code),
+
+    /// Format error
+    Format {
+        /// The source.
+        #[from]
+        source: std::fmt::Error,
+    },
+
+    /// Context is filled with defaults
+    Context {
+        /// The source.
+        #[source(from)]
+        source: std::num::ParseIntError,
+        /// The context.
+        ctx: u8,
+    },
+
+    /// Not a source
+    Unknown,
+}
+
+const DEVICE: Error<2> = Error::from_device(Code(3));
+const FORMAT: Error<2> = Error::from_format(std::fmt::Error);
+
+fn main() {
+    assert!(matches!(DEVICE, Error::Device(Code(3))));
+    assert!(matches!(FORMAT, Error::Format { .. }));
+}
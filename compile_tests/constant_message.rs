@@ -0,0 +1,40 @@
+#[derive(Debug, onlyerror::Error)]
+#[error("operation failed")]
+enum Error {
+    /// The file was not found.
+    NotFound,
+
+    /// Timed out after {0} seconds.
+    Timeout(u64),
+
+    /// Invalid input {input}.
+    Invalid {
+        input: String,
+        #[source]
+        source: std::num::ParseIntError,
+    },
+
+    /// Happens when {x} is bad, which is not a field.
+    Bad { y: u8 },
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error(prefix = "app: ")]
+#[error("{{unreachable}}")]
+enum Prefixed {
+    Broken,
+}
+
+fn main() {
+    assert_eq!(Error::NotFound.to_string(), "operation failed");
+    assert_eq!(Error::Timeout(30).to_string(), "operation failed");
+    let err = Error::Invalid {
+        input: "x".to_string(),
+        source: "x".parse::<u8>().unwrap_err(),
+    };
+    assert_eq!(err.to_string(), "operation failed");
+    assert!(std::error::Error::source(&err).is_some());
+    assert_eq!(Error::Bad { y: 1 }.to_string(), "operation failed");
+
+    assert_eq!(Prefixed::Broken.to_string(), "app: {unreachable}");
+}
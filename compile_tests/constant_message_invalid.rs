@@ -0,0 +1,19 @@
+#[derive(Debug, onlyerror::Error)]
+#[error("operation failed")]
+enum Message {
+    NotFound,
+
+    #[error("Timed out after {0} seconds")]
+    Timeout(u64),
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error("operation failed")]
+enum Transparent {
+    NotFound,
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+#[derive(onlyerror::Error)]
+#[error(debug_as_display)]
+enum Error<T: std::fmt::Display> {
+    #[error("Invalid token {0}")]
+    Token(T),
+
+    /// Unexpected end of input
+    Eof,
+}
+
+fn main() {
+    let err = Error::Token("`)`");
+    assert_eq!(format!("{err:?}"), "Invalid token `)`");
+    assert_eq!(format!("{:?}", Error::<u8>::Eof), "Unexpected end of input");
+
+    let result: Result<(), Error<char>> = Err(Error::Eof);
+    assert_eq!(
+        format!("{result:?}"),
+        "Err(Unexpected end of input)",
+    );
+}
@@ -0,0 +1,9 @@
+#[derive(onlyerror::Error)]
+#[derive(Debug)]
+#[error(debug_as_display)]
+enum Error {
+    /// Unexpected end of input
+    Eof,
+}
+
+fn main() {}
@@ -0,0 +1,41 @@
+#[derive(Debug, onlyerror::Error)]
+#[error("error in {variant}")]
+enum Error {
+    NotFound,
+
+    Timeout(u64),
+
+    Invalid { variant: String },
+
+    /// Permission denied
+    Denied,
+
+    #[error("Code {0}")]
+    Code(u16),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error(prefix = "app: ")]
+#[error("{{{variant:>8}}}")]
+enum Padded {
+    Short,
+}
+
+fn main() {
+    assert_eq!(Error::NotFound.to_string(), "error in NotFound");
+    assert_eq!(Error::Timeout(30).to_string(), "error in Timeout");
+    let err = Error::Invalid {
+        variant: "field".to_string(),
+    };
+    assert_eq!(err.to_string(), "error in Invalid");
+    assert_eq!(Error::Denied.to_string(), "Permission denied");
+    assert_eq!(Error::Code(404).to_string(), "Code 404");
+
+    let err = Error::from(std::io::Error::other("disk"));
+    assert_eq!(err.to_string(), "disk");
+
+    assert_eq!(Padded::Short.to_string(), "app: {   Short}");
+}
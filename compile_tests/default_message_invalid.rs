@@ -0,0 +1,20 @@
+#[derive(Debug, onlyerror::Error)]
+#[error("error in {variant}: {field}")]
+enum Reference {
+    Struct { field: u8 },
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error("error in {}", name())]
+enum Arguments {
+    Unit,
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error(chain_source)]
+#[error("error in {variant}")]
+enum ChainSource {
+    Io(#[from] std::io::Error),
+}
+
+fn main() {}
@@ -0,0 +1,47 @@
+//! Deprecated variants must not trip `deprecated` in generated code.
+
+#![deny(deprecated)]
+
+#[derive(Debug, onlyerror::Error)]
+#[error(predicates, const_new, eq, chain)]
+enum Error {
+    /// Not found
+    #[deprecated = "use `Missing` instead"]
+    #[error(code = 1)]
+    NotFound,
+
+    /// Missing {0}
+    #[error(code = 2)]
+    Missing(String),
+
+    /// I/O error
+    #[deprecated]
+    #[error(code = 3)]
+    Io(#[from] std::io::Error),
+
+    /// Formatting failed
+    #[deprecated(note = "use `Missing` instead")]
+    #[error(code = 4)]
+    Fmt(#[from] std::fmt::Error),
+
+    #[deprecated(since = "0.2.0")]
+    #[error(skip_display)]
+    #[error(code = 5)]
+    Custom,
+}
+
+impl Error {
+    #[allow(deprecated)]
+    fn fmt_custom(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("custom")
+    }
+}
+
+fn main() {
+    let err = Error::Missing("config".into());
+    assert_eq!(err.to_string(), "Missing config");
+    assert!(err.is_missing());
+    assert_eq!(err.code(), 2);
+    assert_eq!(err.chain().count(), 1);
+    assert_eq!(err, Error::Missing("config".into()));
+}
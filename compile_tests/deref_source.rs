@@ -0,0 +1,52 @@
+use std::error::Error as _;
+use std::ops::Deref;
+use std::rc::Rc;
+
+/// A custom smart pointer around a dynamic error.
+#[derive(Debug)]
+struct Shared(Box<dyn std::error::Error + 'static>);
+
+impl Deref for Shared {
+    type Target = dyn std::error::Error + 'static;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Inner {
+    /// Inner error
+    Failed,
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    /// Counted error
+    Counted(#[from] Rc<dyn std::error::Error>),
+
+    /// Custom pointer
+    Custom(#[from(deref)] Shared),
+
+    /// Custom pointer with context
+    Context {
+        context: String,
+        #[source(deref)]
+        source: Shared,
+    },
+}
+
+fn main() {
+    let inner: Rc<dyn std::error::Error> = Rc::new(Inner::Failed);
+    let err = Error::from(Rc::clone(&inner));
+    assert!(err.source().unwrap().is::<Inner>());
+
+    let err = Error::from(Shared(Box::new(Inner::Failed)));
+    assert!(err.source().unwrap().is::<Inner>());
+
+    let err = Error::Context {
+        context: "loading".into(),
+        source: Shared(Box::new(Inner::Failed)),
+    };
+    assert!(err.source().unwrap().is::<Inner>());
+}
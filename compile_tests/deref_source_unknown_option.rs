@@ -0,0 +1,7 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    /// I/O error
+    Io(#[source(borrow)] std::io::Error),
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use std::fmt;
+
+#[derive(Debug, onlyerror::Error)]
+#[error(delegate = describe)]
+enum Error {
+    NotFound(String),
+    Timeout { secs: u64 },
+}
+
+impl Error {
+    fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(what) => write!(f, "{what} was not found"),
+            Self::Timeout { secs } => write!(f, "timed out after {secs}s"),
+        }
+    }
+}
+
+fn main() {
+    assert_eq!(
+        Error::NotFound("config".into()).to_string(),
+        "config was not found",
+    );
+    assert_eq!(Error::Timeout { secs: 3 }.to_string(), "timed out after 3s");
+}
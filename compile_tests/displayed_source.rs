@@ -0,0 +1,34 @@
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+enum SubError {
+    #[error("sub error {0}")]
+    Code(u8),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("wrap: {inner}")]
+    Wrap {
+        #[from]
+        inner: SubError,
+    },
+
+    #[error("wrap {0}: {1}")]
+    Tuple(&'static str, #[source] SubError),
+}
+
+fn main() {
+    let err = Error::from(SubError::Code(7));
+    assert_eq!(err.to_string(), "wrap: sub error 7");
+    let source = err.source().unwrap();
+    assert_eq!(source.to_string(), "sub error 7");
+    assert!(matches!(source.downcast_ref(), Some(SubError::Code(7))));
+
+    let err = Error::Tuple("tuple", SubError::Code(8));
+    assert_eq!(err.to_string(), "wrap tuple: sub error 8");
+    assert!(matches!(
+        err.source().unwrap().downcast_ref(),
+        Some(SubError::Code(8)),
+    ));
+}
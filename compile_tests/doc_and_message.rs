@@ -0,0 +1,30 @@
+//! The doc comment documents the variant while `#[error]` drives `Display`.
+
+#![deny(missing_docs)]
+
+/// Public error type.
+#[derive(Debug, onlyerror::Error)]
+pub enum Error {
+    /// Returned when the configuration file cannot be found in any search path.
+    #[error("different")]
+    NotFound,
+
+    /// Returned when the configuration file is malformed.
+    #[error("line {line}: {msg}")]
+    Malformed {
+        /// Line number.
+        line: usize,
+        /// Parser message.
+        msg: String,
+    },
+}
+
+fn main() {
+    assert_eq!(Error::NotFound.to_string(), "different");
+
+    let err = Error::Malformed {
+        line: 3,
+        msg: "unexpected `=`".into(),
+    };
+    assert_eq!(err.to_string(), "line 3: unexpected `=`");
+}
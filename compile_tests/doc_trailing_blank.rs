@@ -0,0 +1,29 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    /// Connection lost
+    ///
+    Lost,
+
+    /// Timed out
+    ///
+    ///
+    TimedOut,
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error(multiline_docs)]
+enum Block {
+    /// Connection lost.
+    /// Try again later.
+    ///
+    Lost,
+}
+
+fn main() {
+    assert_eq!(Error::Lost.to_string(), "Connection lost");
+    assert_eq!(Error::TimedOut.to_string(), "Timed out");
+    assert_eq!(
+        Block::Lost.to_string(),
+        "Connection lost.\nTry again later.",
+    );
+}
@@ -0,0 +1,24 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    ///No leading space.
+    ///Second line.
+    Tight,
+
+    ///   Indented   with    extra
+    ///     spaces.
+    Loose,
+
+    /** Block comment
+    split across
+    lines. */
+    Block,
+}
+
+fn main() {
+    assert_eq!(Error::Tight.to_string(), "No leading space. Second line.");
+    assert_eq!(Error::Loose.to_string(), "Indented with extra spaces.");
+    assert_eq!(
+        Error::Block.to_string(),
+        "Block comment split across lines."
+    );
+}
@@ -0,0 +1,13 @@
+macro_rules! error {
+    ($($field:ident: $ty:ty),*) => {
+        #[derive(Debug, onlyerror::Error)]
+        enum Error {
+            /// Invalid
+            Invalid { $($field: $ty),* },
+        }
+    };
+}
+
+error!(line: usize, line: u32);
+
+fn main() {}
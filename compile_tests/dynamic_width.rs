@@ -0,0 +1,59 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("[{value:>width$}]")]
+    Aligned { value: u32, width: usize },
+
+    #[error("[{value:.prec$}] to {prec} places")]
+    Precise { value: f64, prec: usize },
+
+    #[error("[{0:>1$}]")]
+    Tuple(String, usize),
+
+    #[error("[{code:0width$}]", width = 4)]
+    Explicit { code: u8 },
+
+    #[error("[{code:>1$}] {0}", "ignored", 5)]
+    Positional { code: u8 },
+
+    #[error("«{{{0:é^1$}}}» → {{日本}}")]
+    Escaped(char, usize),
+
+    #[error("{größe:>breite$.präzision$} 🦀")]
+    Unicode {
+        größe: f64,
+        breite: usize,
+        präzision: usize,
+    },
+
+    #[error("{label}: {:.*}", 2, 1.23456)]
+    Star { label: &'static str },
+}
+
+fn main() {
+    let err = Error::Aligned {
+        value: 42,
+        width: 5,
+    };
+    assert_eq!(err.to_string(), "[   42]");
+
+    let err = Error::Precise {
+        value: 1.23456,
+        prec: 2,
+    };
+    assert_eq!(err.to_string(), "[1.23] to 2 places");
+
+    let err = Error::Tuple("ab".to_string(), 4);
+    assert_eq!(err.to_string(), "[  ab]");
+
+    assert_eq!(Error::Explicit { code: 7 }.to_string(), "[0007]");
+    assert_eq!(Error::Positional { code: 7 }.to_string(), "[    7] ignored");
+    assert_eq!(Error::Escaped('x', 3).to_string(), "«{éxé}» → {日本}");
+
+    let err = Error::Unicode {
+        größe: 1.5,
+        breite: 6,
+        präzision: 2,
+    };
+    assert_eq!(err.to_string(), "  1.50 🦀");
+    assert_eq!(Error::Star { label: "pi" }.to_string(), "pi: 1.23");
+}
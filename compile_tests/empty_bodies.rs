@@ -0,0 +1,41 @@
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+#[error(predicates, eq, chain, append_source)]
+enum Error {
+    /// Empty struct
+    Struct {},
+
+    #[error("Empty tuple")]
+    Tuple(),
+
+    #[error("Empty struct with code")]
+    Coded {},
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error("Empty struct")]
+struct EmptyStruct {}
+
+#[derive(Debug, onlyerror::Error)]
+#[error("Empty tuple struct")]
+struct EmptyTuple();
+
+fn main() {
+    let err = Error::Struct {};
+    assert_eq!(err.to_string(), "Empty struct");
+    assert!(err.source().is_none());
+    assert!(err.is_struct());
+    assert_eq!(err, Error::Struct {});
+    assert_eq!(err.chain().count(), 1);
+
+    let err = Error::Tuple();
+    assert_eq!(err.to_string(), "Empty tuple");
+    assert!(err.source().is_none());
+    assert_ne!(err, Error::Struct {});
+
+    assert_eq!(Error::Coded {}.to_string(), "Empty struct with code");
+
+    assert_eq!(EmptyStruct {}.to_string(), "Empty struct");
+    assert_eq!(EmptyTuple().to_string(), "Empty tuple struct");
+}
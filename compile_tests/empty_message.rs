@@ -0,0 +1,25 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("")]
+    Silent(#[from] std::io::Error),
+
+    /// Loud
+    Loud,
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum AllEmpty {
+    #[error("")]
+    First,
+    #[error("")]
+    Second(usize),
+}
+
+fn main() {
+    let err = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "hidden"));
+    assert_eq!(format!("{err}"), "");
+    assert_eq!(format!("{}", Error::Loud), "Loud");
+
+    assert_eq!(format!("{}", AllEmpty::First), "");
+    assert_eq!(format!("{}", AllEmpty::Second(1)), "");
+}
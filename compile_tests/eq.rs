@@ -0,0 +1,64 @@
+#[derive(Debug, onlyerror::Error)]
+#[error(eq)]
+enum Error {
+    /// I/O error
+    Io(#[from] std::io::Error),
+
+    #[error("Parse error on line {line}")]
+    Parse {
+        #[source]
+        source: std::num::ParseIntError,
+        line: usize,
+    },
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    /// Unknown
+    Unknown,
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error(eq)]
+enum Generic<'a, T: std::fmt::Debug, const N: usize>
+where
+    T: Clone,
+{
+    #[error("Unexpected {0:?}")]
+    Unexpected(T, &'a str),
+
+    #[error("Expected {} items", N)]
+    Count([u8; N]),
+}
+
+fn io(msg: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Other, msg)
+}
+
+fn parse(line: usize) -> Error {
+    let source = "x".parse::<u8>().unwrap_err();
+    Error::Parse { source, line }
+}
+
+fn main() {
+    assert_eq!(Error::Unknown, Error::Unknown);
+    assert_eq!(Error::from(io("a")), Error::from(io("b")));
+    assert_ne!(Error::from(io("a")), Error::Unknown);
+
+    assert_eq!(parse(1), parse(1));
+    assert_ne!(parse(1), parse(2));
+
+    assert_eq!(
+        Error::NotFound("a".to_string()),
+        Error::NotFound("a".to_string())
+    );
+    assert_ne!(
+        Error::NotFound("a".to_string()),
+        Error::NotFound("b".to_string())
+    );
+
+    let err: Generic<char, 2> = Generic::Unexpected('x', "input");
+    assert_eq!(err, Generic::Unexpected('x', "input"));
+    assert_ne!(err, Generic::Unexpected('y', "input"));
+    assert_eq!(Generic::<char, 2>::Count([1, 2]), Generic::Count([1, 2]));
+}
@@ -0,0 +1,32 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    /// Not found
+    #[error(code = 404)]
+    NotFound,
+
+    #[error(code = 0x1F4)]
+    #[error("Internal error: {0}")]
+    Internal(String),
+
+    #[error("Timed out after {secs}s")]
+    #[error(code = 5_04u32)]
+    Timeout { secs: u64 },
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error("Config error")]
+#[error(code = 7)]
+struct ConfigError;
+
+fn main() {
+    assert_eq!(Error::NotFound.code(), 404);
+    assert_eq!(Error::Internal("oops".to_string()).code(), 500);
+    assert_eq!(
+        Error::Internal("oops".to_string()).to_string(),
+        "Internal error: oops"
+    );
+    assert_eq!(Error::Timeout { secs: 3 }.code(), 504);
+    assert_eq!(Error::Timeout { secs: 3 }.to_string(), "Timed out after 3s");
+    assert_eq!(ConfigError.code(), 7);
+    assert_eq!(ConfigError.to_string(), "Config error");
+}
@@ -0,0 +1,29 @@
+#[derive(Debug, onlyerror::Error)]
+enum Missing {
+    /// Not found
+    #[error(code = 404)]
+    NotFound,
+
+    /// Unknown
+    Unknown,
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Duplicate {
+    /// Not found
+    #[error(code = 404)]
+    NotFound,
+
+    /// Gone
+    #[error(code = 404)]
+    Gone,
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Negative {
+    /// Not found
+    #[error(code = -1)]
+    NotFound,
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("Template {{count}} expects {count} items")]
+    Struct { count: usize },
+
+    #[error("Template {{0}} expects {0} items, {{{1}}}")]
+    Tuple(usize, &'static str),
+
+    #[error("Unused {{placeholder}}")]
+    Unit,
+
+    #[error("JSON object {{}} was empty in {path}, }}{{ {{{{}}}}")]
+    Empty { path: String },
+}
+
+fn main() {
+    let err = Error::Struct { count: 3 };
+    assert!(format!("{}", err).contains("{count}"));
+    assert_eq!(err.to_string(), "Template {count} expects 3 items");
+    assert_eq!(
+        Error::Tuple(2, "x").to_string(),
+        "Template {0} expects 2 items, {x}",
+    );
+    assert_eq!(Error::Unit.to_string(), "Unused {placeholder}");
+
+    let err = Error::Empty {
+        path: "a.json".to_string(),
+    };
+    assert_eq!(
+        err.to_string(),
+        "JSON object {} was empty in a.json, }{ {{}}"
+    );
+}
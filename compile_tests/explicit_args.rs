@@ -0,0 +1,23 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn expensive() -> usize {
+    CALLS.fetch_add(1, Ordering::SeqCst) + 1
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("Call {} of {0}: {0}", expensive())]
+    Counted(usize),
+}
+
+fn main() {
+    let err = Error::Counted(42);
+
+    assert_eq!(err.to_string(), "Call 1 of 42: 42");
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+    assert_eq!(err.to_string(), "Call 2 of 42: 42");
+    assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,34 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("mode: {}", if self.verbose { "v" } else { "q" })]
+    Mode { verbose: bool },
+
+    #[error("{} of {total} items: {}", if *done == *total { "all" } else { "some" }, match self.items.len() {
+        0 => String::from("none"),
+        n => n.to_string(),
+    })]
+    Progress {
+        done: usize,
+        total: usize,
+        items: Vec<u8>,
+    },
+}
+
+fn main() {
+    assert_eq!(Error::Mode { verbose: true }.to_string(), "mode: v");
+    assert_eq!(Error::Mode { verbose: false }.to_string(), "mode: q");
+
+    let err = Error::Progress {
+        done: 2,
+        total: 2,
+        items: vec![1, 2],
+    };
+    assert_eq!(err.to_string(), "all of 2 items: 2");
+
+    let err = Error::Progress {
+        done: 0,
+        total: 2,
+        items: vec![],
+    };
+    assert_eq!(err.to_string(), "some of 2 items: none");
+}
@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("len={}", .0.len())]
+    Length(Vec<u8>),
+
+    #[error("Cannot open {}", self.path.display())]
+    Open { path: PathBuf },
+
+    #[error("Missing {} of {}", self.0, .1.join(", "))]
+    Missing(String, Vec<&'static str>),
+
+    #[error("{}", .name.to_uppercase())]
+    Shout { name: String },
+}
+
+fn main() {
+    assert_eq!(Error::Length(vec![1, 2, 3]).to_string(), "len=3");
+
+    let err = Error::Open {
+        path: PathBuf::from("config.toml"),
+    };
+    assert_eq!(err.to_string(), "Cannot open config.toml");
+
+    let err = Error::Missing("b".to_string(), vec!["a", "b", "c"]);
+    assert_eq!(err.to_string(), "Missing b of a, b, c");
+
+    let err = Error::Shout {
+        name: "hello".to_string(),
+    };
+    assert_eq!(err.to_string(), "HELLO");
+}
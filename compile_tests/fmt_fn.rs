@@ -0,0 +1,65 @@
+use std::fmt;
+
+mod format {
+    pub fn list(items: &[String], f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Missing items: ")?;
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(item)?;
+        }
+        Ok(())
+    }
+}
+
+fn range(start: &u32, r#type: &&'static str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{type} starting at {start}")
+}
+
+fn io(source: &std::io::Error, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "I/O: {source}")
+}
+
+fn unit(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("unit")
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error(chain_source)]
+enum Error {
+    /// Ignored doc comment
+    #[error(fmt = format::list)]
+    Missing(Vec<String>),
+
+    #[error(fmt = range)]
+    Range { start: u32, r#type: &'static str },
+
+    #[error(fmt = unit)]
+    Unit,
+
+    #[error(fmt = io)]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error(fmt = unit)]
+struct UnitStruct;
+
+fn main() {
+    let err = Error::Missing(vec!["a".into(), "b".into()]);
+    assert_eq!(err.to_string(), "Missing items: a, b");
+
+    let err = Error::Range {
+        start: 3,
+        r#type: "Block",
+    };
+    assert_eq!(err.to_string(), "Block starting at 3");
+
+    assert_eq!(Error::Unit.to_string(), "unit");
+
+    let err = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "disk full"));
+    assert_eq!(err.to_string(), "I/O: disk full");
+
+    assert_eq!(UnitStruct.to_string(), "unit");
+}
@@ -0,0 +1,19 @@
+fn unit(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str("unit")
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Message {
+    #[error("Not found")]
+    #[error(fmt = unit)]
+    NotFound,
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[no_display]
+enum NoDisplay {
+    #[error(fmt = unit)]
+    NotFound,
+}
+
+fn main() {}
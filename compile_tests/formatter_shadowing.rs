@@ -0,0 +1,9 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("frame {f}")]
+    Frame { f: u32 },
+}
+
+fn main() {
+    assert_eq!(format!("{}", Error::Frame { f: 7 }), "frame 7");
+}
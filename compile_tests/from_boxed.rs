@@ -0,0 +1,33 @@
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+#[error("huge error")]
+struct HugeError {
+    _data: [u8; 256],
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    /// Big error
+    Big(#[from(boxed)] Box<HugeError>),
+
+    #[error("I/O error at {path}")]
+    Io {
+        #[source(from, boxed)]
+        source: std::boxed::Box<std::io::Error>,
+        path: String,
+    },
+}
+
+fn main() {
+    let err = Error::from(HugeError { _data: [0; 256] });
+    assert!(matches!(err, Error::Big(_)));
+    assert!(err.source().unwrap().is::<HugeError>());
+
+    let err = Error::from(Box::new(HugeError { _data: [0; 256] }));
+    assert_eq!(err.to_string(), "Big error");
+
+    let err = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "disk full"));
+    assert_eq!(err.to_string(), "I/O error at ");
+    assert!(err.source().unwrap().is::<std::io::Error>());
+}
@@ -0,0 +1,28 @@
+#[derive(Debug, onlyerror::Error)]
+enum NotBoxed {
+    /// Not boxed
+    Io(#[from(boxed)] std::io::Error),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Unsized {
+    /// Dynamic error
+    Dynamic(#[from(boxed)] Box<dyn std::error::Error + Send + Sync>),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Source {
+    /// Source only
+    Io(#[source(boxed)] Box<std::io::Error>),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Conflict {
+    /// Boxed
+    Boxed(#[from(boxed)] Box<std::io::Error>),
+
+    /// Unboxed
+    Unboxed(#[from] std::io::Error),
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use std::error::Error as _;
+use std::io::{Error as IoError, ErrorKind};
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("disk problem")]
+    Io(#[from] IoError),
+
+    #[error("parse problem")]
+    Parse {
+        #[from]
+        source: std::num::ParseIntError,
+    },
+}
+
+fn main() {
+    let err = Error::from(IoError::new(ErrorKind::Other, "disk full"));
+    assert_eq!(err.to_string(), "disk problem");
+    let source = err.source().unwrap();
+    assert_eq!(source.to_string(), "disk full");
+    assert!(source.downcast_ref::<IoError>().is_some());
+
+    let err = Error::from("x".parse::<u8>().unwrap_err());
+    assert_eq!(err.to_string(), "parse problem");
+    assert!(err.source().unwrap().is::<std::num::ParseIntError>());
+}
@@ -0,0 +1,31 @@
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("Parse error ({1:?})")]
+    Parse(#[from] std::num::ParseIntError, String),
+
+    #[error("I/O error at {path:?}")]
+    Io {
+        #[from]
+        source: std::io::Error,
+        path: Option<String>,
+    },
+}
+
+fn main() {
+    let num = "x".parse::<u8>().unwrap_err();
+    let mut err = Error::from(num);
+    assert_eq!(err.to_string(), r#"Parse error ("")"#);
+    assert!(err.source().unwrap().is::<std::num::ParseIntError>());
+
+    // The default context is overwritten later.
+    if let Error::Parse(_, context) = &mut err {
+        *context = "line 1".to_string();
+    }
+    assert_eq!(err.to_string(), r#"Parse error ("line 1")"#);
+
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "oh no");
+    let err = Error::from(io);
+    assert_eq!(err.to_string(), "I/O error at None");
+}
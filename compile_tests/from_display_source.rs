@@ -0,0 +1,36 @@
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+enum Inner {
+    #[error("inner failed")]
+    Failed(#[source] std::io::Error),
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error(prefix = "outer: ")]
+enum Error {
+    #[error("{0}")]
+    Inner(#[from] Inner),
+
+    #[error("{0:?}")]
+    Debug(#[from] std::fmt::Error),
+
+    #[error("{0}")]
+    Source(#[source] std::io::Error),
+}
+
+fn main() {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "disk full");
+    let err = Error::from(Inner::Failed(io));
+    assert_eq!(err.to_string(), "outer: inner failed");
+    let source = err.source().unwrap();
+    assert_eq!(source.to_string(), "disk full");
+    assert!(source.is::<std::io::Error>());
+
+    let err = Error::from(std::fmt::Error);
+    assert!(err.source().unwrap().is::<std::fmt::Error>());
+
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "disk full");
+    let err = Error::Source(io);
+    assert!(err.source().unwrap().is::<std::io::Error>());
+}
@@ -0,0 +1,64 @@
+use std::error::Error as _;
+use std::fmt::{Debug, Display};
+
+/// Stands in for a generic third-party error like `nom::Err<E>`.
+#[derive(Debug)]
+enum ParseFailure<E> {
+    Incomplete,
+    Failure(E),
+}
+
+impl<E: Display> Display for ParseFailure<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Incomplete => f.write_str("incomplete input"),
+            Self::Failure(err) => write!(f, "parse failure: {err}"),
+        }
+    }
+}
+
+impl<E: Debug + Display> std::error::Error for ParseFailure<E> {}
+
+#[derive(Debug, onlyerror::Error)]
+enum Error<'a, E>
+where
+    E: Debug + Display + 'static,
+{
+    /// Parse error
+    Parse(#[from] ParseFailure<E>),
+
+    #[error("Unexpected input {0:?}")]
+    Input(&'a str),
+}
+
+fn parse<'a>(input: &'a str) -> Result<u8, Error<'a, &'static str>> {
+    if input.is_empty() {
+        return Err(ParseFailure::Incomplete.into());
+    }
+    if input == "?" {
+        return Err(Error::Input(input));
+    }
+
+    Err(ParseFailure::Failure("bad digit").into())
+}
+
+fn main() {
+    let err = parse("").unwrap_err();
+    assert_eq!(err.to_string(), "Parse error");
+    assert_eq!(err.source().unwrap().to_string(), "incomplete input");
+
+    let err = parse("x").unwrap_err();
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        "parse failure: bad digit"
+    );
+    assert!(err.source().unwrap().is::<ParseFailure<&'static str>>());
+
+    assert_eq!(
+        parse("?").unwrap_err().to_string(),
+        r#"Unexpected input "?""#
+    );
+
+    let err: Error<'_, u32> = Error::from(ParseFailure::Failure(7));
+    assert_eq!(err.source().unwrap().to_string(), "parse failure: 7");
+}
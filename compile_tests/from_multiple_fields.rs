@@ -0,0 +1,12 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    /// I/O error
+    Io {
+        #[from]
+        source: std::io::Error,
+        #[from]
+        other: std::fmt::Error,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+use std::error::Error as _;
+use std::path::PathBuf;
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("I/O error at {path:?}")]
+    Io {
+        #[from(with = |source| Self::Io { source, path: PathBuf::from("unknown") })]
+        source: std::io::Error,
+        path: PathBuf,
+    },
+
+    #[error("Invalid number {0}")]
+    Number(#[source(from, with = move |err| Self::Number(err, 10))] std::num::ParseIntError, u32),
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error("Invalid config on line {line}")]
+struct ConfigError {
+    #[from(with = |source| Self { source, line: 1 })]
+    source: std::num::ParseIntError,
+    line: usize,
+}
+
+fn main() {
+    let err = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "oh no"));
+    assert_eq!(err.to_string(), r#"I/O error at "unknown""#);
+    assert!(err.source().unwrap().is::<std::io::Error>());
+
+    let err = Error::from("x".parse::<u8>().unwrap_err());
+    assert!(matches!(err, Error::Number(_, 10)));
+
+    let err = ConfigError::from("x".parse::<u8>().unwrap_err());
+    assert_eq!(err.to_string(), "Invalid config on line 1");
+}
@@ -0,0 +1,30 @@
+#[derive(Debug, onlyerror::Error)]
+enum Variant {
+    /// I/O error
+    Io {
+        #[from(with = |source| Self::Parse { source, line: 0 })]
+        source: std::io::Error,
+        line: usize,
+    },
+
+    /// Parse error
+    Parse {
+        #[source]
+        source: std::io::Error,
+        line: usize,
+    },
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Closure {
+    /// I/O error
+    Io(#[from(with = std::convert::identity)] std::io::Error, u8),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum SourceOnly {
+    /// I/O error
+    Io(#[source(with = |source| Self::Io(source, 0))] std::io::Error, u8),
+}
+
+fn main() {}
@@ -0,0 +1,46 @@
+use std::error::Error as _;
+use std::fmt::{Debug, Display};
+
+#[derive(Debug, onlyerror::Error)]
+enum ParseError<'a, I: Debug + Display, E = std::num::ParseIntError>
+where
+    E: std::error::Error + 'static,
+{
+    #[error("Unexpected input {input} at {position}")]
+    Unexpected { input: I, position: usize },
+
+    #[error("Expected {0:?}")]
+    Expected(&'a str),
+
+    /// Invalid number
+    Number(#[from] E),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Boxed<T>
+where
+    T: std::error::Error + 'static,
+{
+    /// Inner error
+    Inner(#[from] Box<T>),
+}
+
+fn main() {
+    let err: ParseError<char> = ParseError::Unexpected {
+        input: 'x',
+        position: 3,
+    };
+    assert_eq!(err.to_string(), "Unexpected input x at 3");
+
+    let err: ParseError<u8> = ParseError::Expected("digit");
+    assert_eq!(err.to_string(), r#"Expected "digit""#);
+
+    let number = "x".parse::<u8>().unwrap_err();
+    let err: ParseError<char> = ParseError::from(number);
+    assert_eq!(err.to_string(), "Invalid number");
+    assert!(err.source().unwrap().is::<std::num::ParseIntError>());
+
+    let number = "x".parse::<u8>().unwrap_err();
+    let err = Boxed::from(Box::new(number));
+    assert!(err.source().unwrap().is::<std::num::ParseIntError>());
+}
@@ -0,0 +1,26 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("Code {code:0width$}", width = self.pad)]
+    Padded { code: u32, pad: usize },
+
+    #[error("{name:>width$}|{value:.prec$}", width = *pad + 2, prec = self.digits)]
+    Aligned {
+        name: &'static str,
+        value: f64,
+        pad: usize,
+        digits: usize,
+    },
+}
+
+fn main() {
+    let err = Error::Padded { code: 42, pad: 5 };
+    assert_eq!(err.to_string(), "Code 00042");
+
+    let err = Error::Aligned {
+        name: "pi",
+        value: 3.14159,
+        pad: 2,
+        digits: 2,
+    };
+    assert_eq!(err.to_string(), "  pi|3.14");
+}
@@ -0,0 +1,69 @@
+#![deny(missing_docs)]
+//! Kind enums are documented and copied with the visibility of the error.
+
+mod errors {
+    /// A parse error.
+    #[derive(Debug, onlyerror::Error)]
+    #[error(kind)]
+    pub enum ParseError<T: std::fmt::Debug> {
+        /// Unexpected end of input
+        Eof,
+
+        #[error("Unexpected token {0:?}")]
+        Token(T),
+
+        #[error("Invalid number {input}")]
+        Number {
+            input: String,
+            #[source]
+            source: std::num::ParseIntError,
+        },
+
+        #[deprecated]
+        #[error("Unused")]
+        Unused,
+    }
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error(kind, rename_all = "kebab-case")]
+enum HttpError {
+    /// Not found
+    NotFound,
+
+    /// Bad gateway
+    HTTPGateway,
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error(kind)]
+#[error(rename_all = "SCREAMING_SNAKE_CASE")]
+enum ConfigError {
+    /// Missing key
+    MissingKey,
+}
+
+use errors::{ParseError, ParseErrorKind};
+
+fn main() {
+    let err: ParseError<char> = ParseError::Eof;
+    assert_eq!(err.kind(), ParseErrorKind::Eof);
+    assert_eq!(ParseError::Token('x').kind(), ParseErrorKind::Token);
+
+    let err: ParseError<char> = ParseError::Number {
+        input: "x".to_string(),
+        source: "x".parse::<u8>().unwrap_err(),
+    };
+    let kind = err.kind();
+    let copy = kind;
+    assert_eq!(kind, copy);
+    assert_eq!(format!("{kind:?}"), "Number");
+    assert_eq!(kind as u8, 2);
+    assert_eq!(std::mem::size_of::<ParseErrorKind>(), 1);
+    assert_eq!(kind.as_str(), "Number");
+
+    const NAME: &str = HttpErrorKind::NotFound.as_str();
+    assert_eq!(NAME, "not-found");
+    assert_eq!(HttpError::HTTPGateway.kind().as_str(), "http-gateway");
+    assert_eq!(ConfigError::MissingKey.kind().as_str(), "MISSING_KEY");
+}
@@ -0,0 +1,5 @@
+#[derive(Debug, onlyerror::Error)]
+#[error(kind)]
+enum Error {}
+
+fn main() {}
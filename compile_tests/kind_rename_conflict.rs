@@ -0,0 +1,21 @@
+#[derive(Debug, onlyerror::Error)]
+#[error(kind, rename_all = "snake_case")]
+enum Snake {
+    /// I/O error
+    IoError,
+
+    /// Also I/O error
+    IOError,
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error(kind, rename_all = "lowercase")]
+enum Lower {
+    /// Not found
+    NotFound,
+
+    /// Also not found
+    Notfound,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+#[derive(Debug, onlyerror::Error)]
+#[error(kind, rename_all = "Title Case")]
+enum Unknown {
+    /// Not found
+    NotFound,
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error(rename_all = "snake_case")]
+enum NoKind {
+    /// Not found
+    NotFound,
+}
+
+fn main() {}
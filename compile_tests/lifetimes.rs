@@ -0,0 +1,38 @@
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+enum Error<'a, 'b: 'a> {
+    #[error("Unexpected {0:?}")]
+    Unexpected(&'a str),
+
+    #[error("Expected {expected} at {input:?}")]
+    Expected { expected: &'b str, input: &'a str },
+
+    /// Invalid number
+    Number(#[source] &'static std::fmt::Error),
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error("Unexpected {0:?}")]
+struct Token<'a>(&'a str);
+
+fn parse(input: &str) -> Result<(), Error<'_, 'static>> {
+    Err(Error::Expected {
+        expected: "digit",
+        input,
+    })
+}
+
+fn main() {
+    let input = String::from("x");
+    let err = parse(&input).unwrap_err();
+    assert_eq!(err.to_string(), r#"Expected digit at "x""#);
+    assert!(err.source().is_none());
+
+    assert_eq!(Error::Unexpected(&input).to_string(), r#"Unexpected "x""#);
+
+    static FMT: std::fmt::Error = std::fmt::Error;
+    assert!(Error::Number(&FMT).source().is_some());
+
+    assert_eq!(Token(&input).to_string(), r#"Unexpected "x""#);
+}
@@ -0,0 +1,7 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error(stringify!(not found))]
+    NotFound,
+}
+
+fn main() {}
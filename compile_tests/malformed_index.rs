@@ -0,0 +1,25 @@
+#[derive(Debug, onlyerror::Error)]
+enum Negative {
+    #[error("Index {-1}")]
+    Tuple(u8),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Spaces {
+    #[error("Index { 0 }")]
+    Tuple(u8),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Hex {
+    #[error("Index {0x1}")]
+    Tuple(u8, u8),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum DocComment {
+    /// Index {-1}
+    Tuple(u8),
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+//! Generated items must not trip `missing_docs`.
+
+#![deny(missing_docs)]
+
+/// Public error type.
+#[derive(Debug, onlyerror::Error)]
+pub enum Error {
+    /// Not found
+    NotFound,
+
+    /// I/O error
+    Io(#[from] std::io::Error),
+}
+
+fn main() {
+    assert_eq!(Error::NotFound.to_string(), "Not found");
+}
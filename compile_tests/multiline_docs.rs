@@ -0,0 +1,29 @@
+#[derive(Debug, onlyerror::Error)]
+#[error(multiline_docs)]
+enum Error {
+    /// Request failed.
+    /// Check your network connection.
+    Request,
+
+    /// Single line.
+    Single,
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Joined {
+    /// Request failed.
+    /// Check your network connection.
+    Request,
+}
+
+fn main() {
+    assert_eq!(
+        Error::Request.to_string(),
+        "Request failed.\nCheck your network connection.",
+    );
+    assert_eq!(Error::Single.to_string(), "Single line.");
+    assert_eq!(
+        Joined::Request.to_string(),
+        "Request failed. Check your network connection.",
+    );
+}
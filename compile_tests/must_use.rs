@@ -0,0 +1,20 @@
+#![deny(unused_must_use)]
+
+#[derive(Debug, onlyerror::Error)]
+#[error(predicates, const_new)]
+enum Error {
+    /// Timed out
+    #[error(code = 1)]
+    Timeout,
+
+    /// Formatting failed
+    #[error(code = 2)]
+    Fmt(#[from] std::fmt::Error),
+}
+
+fn main() {
+    let err = Error::Timeout;
+    err.is_timeout();
+    err.code();
+    Error::from_fmt(std::fmt::Error);
+}
@@ -0,0 +1,48 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("Line {line} of {total}", line = index + 1)]
+    Struct { index: usize, total: usize },
+
+    #[error("Expected {count} items, got {}", self.len(), count = expected * 2)]
+    Mixed { expected: usize, items: Vec<u8> },
+
+    #[error("{0} is {state}", state = if self.is_on() { "on" } else { "off" })]
+    Tuple(&'static str, bool),
+
+    #[error("Retry {attempt}", attempt = attempt + 1)]
+    Shadowed { attempt: u8 },
+
+    #[error("{value:>width$}", value = "x", width = 3)]
+    Unit,
+}
+
+impl Error {
+    fn is_on(&self) -> bool {
+        matches!(self, Self::Tuple(_, true))
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Mixed { items, .. } => items.len(),
+            _ => 0,
+        }
+    }
+}
+
+fn main() {
+    let err = Error::Struct { index: 0, total: 3 };
+    assert_eq!(err.to_string(), "Line 1 of 3");
+
+    let err = Error::Mixed {
+        expected: 2,
+        items: vec![1],
+    };
+    assert_eq!(err.to_string(), "Expected 4 items, got 1");
+
+    assert_eq!(Error::Tuple("Power", true).to_string(), "Power is on");
+
+    let err = Error::Shadowed { attempt: 1 };
+    assert_eq!(err.to_string(), "Retry 2");
+
+    assert_eq!(Error::Unit.to_string(), "  x");
+}
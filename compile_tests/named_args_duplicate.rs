@@ -0,0 +1,13 @@
+#[derive(Debug, onlyerror::Error)]
+enum Named {
+    #[error("Line {line}", line = index + 1, line = index)]
+    Struct { index: usize },
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Alias {
+    #[error("{name} {name}", name = 0, name = 1)]
+    Tuple(u8, u8),
+}
+
+fn main() {}
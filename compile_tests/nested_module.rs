@@ -0,0 +1,27 @@
+mod errors {
+    pub mod io {
+        #[derive(Debug, onlyerror::Error)]
+        pub enum Error {
+            #[error("Unable to open {0}")]
+            Open(String),
+
+            /// I/O error
+            Io(#[from] std::io::Error),
+        }
+    }
+
+    pub use self::io::Error as IoError;
+}
+
+pub use errors::IoError;
+
+fn main() {
+    use std::error::Error as _;
+
+    let err = IoError::Open("a.txt".into());
+    assert_eq!(err.to_string(), "Unable to open a.txt");
+
+    let err = IoError::from(std::io::Error::new(std::io::ErrorKind::Other, "io"));
+    assert_eq!(err.to_string(), "I/O error");
+    assert!(err.source().is_some());
+}
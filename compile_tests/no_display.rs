@@ -14,4 +14,22 @@ impl core::fmt::Display for Error {
     }
 }
 
-fn main() {}
+#[derive(Debug, onlyerror::Error)]
+#[no_display]
+enum Generic<'a, T>
+where
+    T: core::fmt::Debug,
+{
+    Value(&'a T),
+}
+
+impl<T: core::fmt::Debug> core::fmt::Display for Generic<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        let Self::Value(value) = self;
+        write!(f, "Value {value:?}")
+    }
+}
+
+fn main() {
+    assert_eq!(Generic::Value(&3).to_string(), "Value 3");
+}
@@ -0,0 +1,42 @@
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+#[no_error_impl]
+#[error(predicates)]
+enum Error {
+    #[error("Invalid number {0:?}")]
+    Number(String, #[source] std::num::ParseIntError),
+
+    /// I/O error
+    Io(#[from] std::io::Error),
+}
+
+// Only I/O errors are exposed as a source.
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Number(..) => None,
+        }
+    }
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[no_error_impl]
+#[error("Config error")]
+struct ConfigError;
+
+impl std::error::Error for ConfigError {}
+
+fn main() {
+    let err = Error::Number("x".into(), "x".parse::<u8>().unwrap_err());
+    assert_eq!(err.to_string(), r#"Invalid number "x""#);
+    assert!(err.source().is_none());
+    assert!(err.is_number());
+
+    let err = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "disk"));
+    assert_eq!(err.to_string(), "I/O error");
+    assert!(err.source().unwrap().is::<std::io::Error>());
+
+    assert!(ConfigError.source().is_none());
+}
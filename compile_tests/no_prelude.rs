@@ -0,0 +1,35 @@
+#[no_implicit_prelude]
+mod errors {
+    #[derive(::core::fmt::Debug, ::onlyerror::Error)]
+    pub enum Error {
+        /// Unit error
+        Unit,
+
+        #[error("Code {0}")]
+        Code(u32),
+
+        #[error("Missing {path}")]
+        Missing { path: ::std::string::String },
+
+        /// I/O error
+        Io(#[from] ::std::io::Error),
+    }
+
+    #[derive(::core::fmt::Debug, ::onlyerror::Error)]
+    pub enum Empty {}
+}
+
+fn main() {
+    use std::error::Error as _;
+
+    assert_eq!(errors::Error::Unit.to_string(), "Unit error");
+    assert_eq!(errors::Error::Code(7).to_string(), "Code 7");
+    let err = errors::Error::Missing {
+        path: "a.txt".into(),
+    };
+    assert_eq!(err.to_string(), "Missing a.txt");
+
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "io");
+    let err = errors::Error::from(io);
+    assert_eq!(err.source().unwrap().to_string(), "io");
+}
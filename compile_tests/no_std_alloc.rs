@@ -0,0 +1,35 @@
+#![no_std]
+
+extern crate alloc;
+extern crate std;
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString as _};
+use core::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+enum Inner {
+    #[error("Inner error: {0}")]
+    Message(String),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    /// Dynamic error
+    Dynamic(#[source] Box<dyn core::error::Error + 'static>),
+
+    /// Thread-safe dynamic error
+    Shared(#[from] Box<dyn core::error::Error + Send + Sync>),
+}
+
+fn main() {
+    let err = Error::Dynamic(Box::new(Inner::Message("dynamic".into())));
+    assert_eq!(err.source().unwrap().to_string(), "Inner error: dynamic");
+
+    let inner: Box<dyn core::error::Error + Send + Sync> =
+        Box::new(Inner::Message("shared".into()));
+    let err = Error::from(inner);
+    let source = err.source().unwrap();
+    assert_eq!(source.to_string(), "Inner error: shared");
+    assert!(source.is::<Inner>());
+}
@@ -0,0 +1,36 @@
+#![no_std]
+
+extern crate alloc;
+// Linked for the test harness, but not nameable as `::std` by the generated code.
+extern crate std as _;
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString as _};
+use core::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("Invalid name {name:?}")]
+    Name { name: String },
+
+    /// Dynamic error
+    Dynamic(#[from] Box<dyn core::error::Error + Send + Sync>),
+
+    /// Formatting error
+    Fmt(#[from(boxed)] Box<core::fmt::Error>),
+}
+
+fn main() {
+    let err = Error::Name {
+        name: "x".to_string(),
+    };
+    assert_eq!(err.to_string(), r#"Invalid name "x""#);
+    assert!(err.source().is_none());
+
+    let inner: Box<dyn core::error::Error + Send + Sync> = Box::new(core::fmt::Error);
+    let err = Error::from(inner);
+    assert!(err.source().unwrap().is::<core::fmt::Error>());
+
+    let err = Error::from(core::fmt::Error);
+    assert!(matches!(err, Error::Fmt(_)));
+}
@@ -0,0 +1,32 @@
+#![no_std]
+
+extern crate std;
+
+use core::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+enum Inner {
+    /// Inner error
+    Failed,
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    /// Wrapped error
+    Wrapped(#[from] Inner),
+
+    /// No source
+    Leaf,
+}
+
+fn main() {
+    // The derived method must coerce to the exact trait signature.
+    let source: fn(&Error) -> Option<&(dyn core::error::Error + 'static)> =
+        <Error as core::error::Error>::source;
+
+    let err = Error::from(Inner::Failed);
+    let inner = source(&err).unwrap();
+    assert!(inner.is::<Inner>());
+    assert!(<Error as core::error::Error>::source(&Error::Leaf).is_none());
+    assert!(err.source().unwrap().source().is_none());
+}
@@ -0,0 +1,29 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("x")]
+    A(u8),
+    #[error("y")]
+    B
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Single {
+    #[error("x")]
+    U
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[allow(dead_code)]
+enum Discriminant {
+    /// First
+    First = 1,
+    /// Second
+    Second = 2
+}
+
+fn main() {
+    assert_eq!(Error::A(1).to_string(), "x");
+    assert_eq!(Error::B.to_string(), "y");
+    assert_eq!(Single::U.to_string(), "x");
+    assert_eq!(Discriminant::Second.to_string(), "Second");
+}
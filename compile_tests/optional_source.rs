@@ -0,0 +1,40 @@
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+#[error(append_source)]
+enum Error {
+    #[error("Retry {attempt} failed")]
+    Retry {
+        attempt: u32,
+        #[source]
+        cause: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    /// Parse failed
+    Parse(#[source] Option<std::num::ParseIntError>),
+
+    /// Formatting failed
+    Fmt(#[source] std::option::Option<std::sync::Arc<std::fmt::Error>>),
+}
+
+fn main() {
+    let err = Error::Retry {
+        attempt: 2,
+        cause: None,
+    };
+    assert_eq!(err.to_string(), "Retry 2 failed");
+    assert!(err.source().is_none());
+
+    let err = Error::Retry {
+        attempt: 3,
+        cause: Some(Box::new(std::fmt::Error)),
+    };
+    assert!(err.source().unwrap().is::<std::fmt::Error>());
+
+    let err = Error::Parse(Some("x".parse::<u8>().unwrap_err()));
+    assert!(err.source().unwrap().is::<std::num::ParseIntError>());
+    assert!(Error::Parse(None).source().is_none());
+
+    let err = Error::Fmt(Some(std::sync::Arc::new(std::fmt::Error)));
+    assert!(err.source().unwrap().is::<std::fmt::Error>());
+}
@@ -0,0 +1,7 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    /// Parse failed
+    Parse(#[from] Option<std::num::ParseIntError>),
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("{0}% complete, costs ${1} for #{2}{0}%")]
+    Tuple(u8, u32, u16),
+
+    #[error("#{id}: ${cost}% ({id:#x})")]
+    Struct { id: u32, cost: u8 },
+}
+
+fn main() {
+    assert_eq!(
+        Error::Tuple(50, 10, 3).to_string(),
+        "50% complete, costs $10 for #350%",
+    );
+    assert_eq!(
+        Error::Struct { id: 255, cost: 9 }.to_string(),
+        "#255: $9% (0xff)",
+    );
+}
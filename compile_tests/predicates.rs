@@ -0,0 +1,37 @@
+#![deny(missing_docs)]
+//! Predicate methods are documented.
+
+/// Errors.
+#[derive(Debug, onlyerror::Error)]
+#[error(predicates)]
+pub enum Error<T: std::fmt::Debug> {
+    /// I/O error
+    Io(#[from] std::io::Error),
+
+    /// Not found
+    NotFound,
+
+    /// Invalid input
+    InvalidInput {
+        /// The input.
+        input: T,
+    },
+
+    /// Old HTTP error
+    #[deprecated]
+    HTTPStatus(u16),
+}
+
+fn main() {
+    let err: Error<u8> = Error::NotFound;
+    assert!(err.is_not_found());
+    assert!(!err.is_io());
+
+    let err: Error<u8> = Error::InvalidInput { input: 1 };
+    assert!(err.is_invalid_input());
+    assert!(!err.is_not_found());
+
+    #[allow(deprecated)]
+    let err: Error<u8> = Error::HTTPStatus(404);
+    assert!(err.is_http_status());
+}
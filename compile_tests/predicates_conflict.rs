@@ -0,0 +1,11 @@
+#[derive(Debug, onlyerror::Error)]
+#[error(predicates)]
+enum Error {
+    /// I/O error
+    IoError,
+
+    /// Also I/O error
+    IOError,
+}
+
+fn main() {}
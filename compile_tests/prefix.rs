@@ -0,0 +1,46 @@
+#[derive(Debug, onlyerror::Error)]
+#[error(prefix = "myapp: ")]
+enum Error {
+    #[error("Cannot open {0}")]
+    Open(String),
+
+    /// Unknown error
+    Unknown,
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error(prefix = "{config} ")]
+enum Braces {
+    /// Missing {0}
+    Missing(u8),
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[no_display]
+#[error(prefix = "ignored: ")]
+enum Hidden {
+    Unknown,
+}
+
+impl std::fmt::Display for Hidden {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("hidden")
+    }
+}
+
+fn main() {
+    assert_eq!(
+        Error::Open("a.txt".to_string()).to_string(),
+        "myapp: Cannot open a.txt"
+    );
+    assert_eq!(Error::Unknown.to_string(), "myapp: Unknown error");
+
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "oh no");
+    assert_eq!(Error::from(io).to_string(), "oh no");
+
+    assert_eq!(Braces::Missing(3).to_string(), "{config} Missing 3");
+    assert_eq!(Hidden::Unknown.to_string(), "hidden");
+}
@@ -0,0 +1,38 @@
+#![feature(error_generic_member_access)]
+
+use std::backtrace::Backtrace;
+use std::error::{request_ref, request_value};
+
+#[derive(Debug, PartialEq)]
+struct Code(u16);
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("Request failed with {0:?}")]
+    Request(#[provide] Code, String),
+
+    /// I/O error
+    Io {
+        #[from]
+        source: std::io::Error,
+        #[backtrace]
+        backtrace: Backtrace,
+    },
+
+    /// Unknown
+    Unknown,
+}
+
+fn main() {
+    let err = Error::Request(Code(404), "/".into());
+    assert_eq!(request_ref::<Code>(&err), Some(&Code(404)));
+    assert!(request_ref::<Backtrace>(&err).is_none());
+
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "oops");
+    let err = Error::from(io);
+    assert!(request_ref::<Backtrace>(&err).is_some());
+    assert!(request_ref::<Code>(&err).is_none());
+
+    assert!(request_ref::<Code>(&Error::Unknown).is_none());
+    assert!(request_value::<u8>(&Error::Unknown).is_none());
+}
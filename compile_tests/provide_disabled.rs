@@ -0,0 +1,7 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("Request failed with {0}")]
+    Request(#[provide] u16),
+}
+
+fn main() {}
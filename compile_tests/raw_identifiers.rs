@@ -0,0 +1,43 @@
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("Unknown type {type} in {match}")]
+    Unknown { r#type: String, r#match: u8 },
+
+    #[error("Bad type {}", self.r#type.len())]
+    Length { r#type: String },
+
+    /// Invalid
+    r#Invalid {
+        #[source]
+        r#ref: std::io::Error,
+    },
+
+    /// Unit
+    r#Unit,
+
+    #[error("{type:?}")]
+    Debug { r#type: u8 },
+}
+
+fn main() {
+    let err = Error::Unknown {
+        r#type: "x".to_string(),
+        r#match: 1,
+    };
+    assert_eq!(err.to_string(), "Unknown type x in 1");
+
+    let err = Error::Length {
+        r#type: "abc".to_string(),
+    };
+    assert_eq!(err.to_string(), "Bad type 3");
+
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "oh no");
+    let err = Error::Invalid { r#ref: io };
+    assert_eq!(err.to_string(), "Invalid");
+    assert_eq!(err.source().unwrap().to_string(), "oh no");
+
+    assert_eq!(Error::Unit.to_string(), "Unit");
+    assert_eq!(Error::Debug { r#type: 1 }.to_string(), "1");
+}
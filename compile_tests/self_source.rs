@@ -0,0 +1,29 @@
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    /// Root cause
+    Root,
+
+    /// Chained error
+    Chain {
+        #[source]
+        prev: Box<Self>,
+    },
+
+    /// Wrapped error
+    Wrapped(#[from] Box<Self>),
+}
+
+fn main() {
+    let err = Error::Chain {
+        prev: Box::new(Error::Root),
+    };
+    let source = err.source().unwrap();
+    assert!(matches!(source.downcast_ref(), Some(Error::Root)));
+
+    let err = Error::from(Box::new(err));
+    assert_eq!(err.to_string(), "Wrapped error");
+    let source = err.source().unwrap();
+    assert!(matches!(source.downcast_ref(), Some(Error::Chain { .. })));
+}
@@ -0,0 +1,43 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    /// Not found
+    NotFound,
+
+    #[error(skip_display)]
+    RateLimited { retry_after: Option<u64> },
+
+    #[error(skip_display)]
+    Status(u16),
+}
+
+impl Error {
+    fn fmt_rate_limited(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RateLimited {
+                retry_after: Some(secs),
+            } => write!(f, "Rate limited, retry in {secs}s"),
+            _ => f.write_str("Rate limited"),
+        }
+    }
+
+    fn fmt_status(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Self::Status(code) = self {
+            write!(f, "HTTP {code}")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn main() {
+    assert_eq!(Error::NotFound.to_string(), "Not found");
+
+    let err = Error::RateLimited {
+        retry_after: Some(3),
+    };
+    assert_eq!(err.to_string(), "Rate limited, retry in 3s");
+    let err = Error::RateLimited { retry_after: None };
+    assert_eq!(err.to_string(), "Rate limited");
+
+    assert_eq!(Error::Status(404).to_string(), "HTTP 404");
+}
@@ -0,0 +1,14 @@
+#[derive(Debug, onlyerror::Error)]
+#[no_display]
+enum Error {
+    #[error(skip_display)]
+    NotFound,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Not found")
+    }
+}
+
+fn main() {}
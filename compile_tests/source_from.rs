@@ -0,0 +1,26 @@
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("I/O error: {ctx:?}")]
+    Io {
+        #[source(from)]
+        source: std::io::Error,
+        ctx: Option<String>,
+    },
+
+    #[error("Parse error on line {1}")]
+    Parse(#[source(from)] std::num::ParseIntError, usize),
+}
+
+fn main() {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "oh no");
+    let err = Error::from(io);
+    assert_eq!(err.to_string(), "I/O error: None");
+    assert_eq!(err.source().unwrap().to_string(), "oh no");
+
+    let num = "x".parse::<u8>().unwrap_err();
+    let err = Error::from(num);
+    assert_eq!(err.to_string(), "Parse error on line 0");
+    assert!(err.source().unwrap().is::<std::num::ParseIntError>());
+}
@@ -0,0 +1,14 @@
+#[derive(Debug)]
+struct Context;
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    /// I/O error
+    Io {
+        #[source(from)]
+        source: std::io::Error,
+        ctx: Context,
+    },
+}
+
+fn main() {}
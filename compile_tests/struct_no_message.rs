@@ -0,0 +1,6 @@
+#[derive(Debug, onlyerror::Error)]
+struct Error {
+    code: u8,
+}
+
+fn main() {}
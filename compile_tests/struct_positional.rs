@@ -0,0 +1,44 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("Not found: {0}")]
+    NotFound { path: String },
+
+    #[error("{1} of {0:?} after {retries} retries")]
+    Failed {
+        host: String,
+        code: u16,
+        retries: u8,
+    },
+
+    #[error("Invalid {0}")]
+    Invalid { r#type: &'static str },
+
+    #[error("[{0:>1$}]")]
+    Aligned { value: u8, width: usize },
+
+    #[error("Explicit {0} for {name}", "args")]
+    Explicit { name: &'static str },
+}
+
+fn main() {
+    let err = Error::NotFound {
+        path: "a.txt".to_string(),
+    };
+    assert_eq!(err.to_string(), "Not found: a.txt");
+
+    let err = Error::Failed {
+        host: "example.com".to_string(),
+        code: 503,
+        retries: 3,
+    };
+    assert_eq!(err.to_string(), r#"503 of "example.com" after 3 retries"#);
+
+    let err = Error::Invalid { r#type: "header" };
+    assert_eq!(err.to_string(), "Invalid header");
+
+    let err = Error::Aligned { value: 7, width: 3 };
+    assert_eq!(err.to_string(), "[  7]");
+
+    let err = Error::Explicit { name: "x" };
+    assert_eq!(err.to_string(), "Explicit args for x");
+}
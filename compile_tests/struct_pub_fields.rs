@@ -0,0 +1,28 @@
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+#[error("Config file {path} is invalid on line {line}")]
+pub struct ConfigError {
+    pub path: String,
+    pub(crate) line: usize,
+    #[source]
+    pub source: std::num::ParseIntError,
+}
+
+#[derive(Debug, onlyerror::Error)]
+#[error("Parse error")]
+pub struct ParseError(#[from] pub std::num::ParseIntError, pub(crate) u8);
+
+fn main() {
+    let err = ConfigError {
+        path: "app.toml".to_string(),
+        line: 3,
+        source: "x".parse::<u8>().unwrap_err(),
+    };
+    assert_eq!(err.to_string(), "Config file app.toml is invalid on line 3");
+    assert!(err.source().is_some());
+
+    let err = ParseError::from("x".parse::<u8>().unwrap_err());
+    assert_eq!(err.1, 0);
+    assert!(err.source().unwrap().is::<std::num::ParseIntError>());
+}
@@ -0,0 +1,57 @@
+use std::error::Error as _;
+use std::path::PathBuf;
+
+#[derive(Debug, onlyerror::Error)]
+#[error("Config file {path:?} is invalid")]
+pub struct ConfigError {
+    path: PathBuf,
+    #[source]
+    source: std::io::Error,
+}
+
+/// Unit error
+#[derive(Debug, onlyerror::Error)]
+struct UnitError;
+
+/// I/O error
+#[derive(Debug, onlyerror::Error)]
+struct IoError(#[from] std::io::Error);
+
+#[derive(Debug, onlyerror::Error)]
+#[error("Value {0} out of range")]
+struct RangeError<T>(T)
+where
+    T: std::fmt::Debug + std::fmt::Display;
+
+#[derive(Debug, onlyerror::Error)]
+#[error(transparent)]
+struct Transparent {
+    inner: std::num::ParseIntError,
+}
+
+fn main() {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "oops");
+    let err = ConfigError {
+        path: "app.toml".into(),
+        source: io,
+    };
+    assert_eq!(err.to_string(), r#"Config file "app.toml" is invalid"#);
+    assert_eq!(err.source().unwrap().to_string(), "oops");
+
+    assert_eq!(UnitError.to_string(), "Unit error");
+    assert!(UnitError.source().is_none());
+
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "oops");
+    let err = IoError::from(io);
+    assert_eq!(err.to_string(), "I/O error");
+    assert!(err.source().unwrap().is::<std::io::Error>());
+
+    assert_eq!(RangeError(300).to_string(), "Value 300 out of range");
+
+    let inner = "x".parse::<u8>().unwrap_err();
+    let err = Transparent {
+        inner: inner.clone(),
+    };
+    assert_eq!(err.to_string(), inner.to_string());
+    assert!(err.source().is_none());
+}
@@ -0,0 +1,47 @@
+use std::error::Error as _;
+
+#[derive(Debug, onlyerror::Error)]
+enum Inner {
+    #[error("inner failed")]
+    Failed(#[source] std::io::Error),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Inner(#[from] Inner),
+
+    #[error(transparent)]
+    Boxed {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    /// Unknown
+    Unknown,
+}
+
+fn main() {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "disk full");
+    let err = Error::from(io);
+    assert_eq!(err.to_string(), "disk full");
+    assert!(err.source().is_none());
+
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "disk full");
+    let err = Error::from(Inner::Failed(io));
+    assert_eq!(err.to_string(), "inner failed");
+    let source = err.source().unwrap();
+    assert_eq!(source.to_string(), "disk full");
+    assert!(source.is::<std::io::Error>());
+
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "disk full");
+    let err = Error::Boxed {
+        source: Box::new(Inner::Failed(io)),
+    };
+    assert_eq!(err.to_string(), "inner failed");
+    assert!(err.source().unwrap().is::<std::io::Error>());
+
+    assert!(Error::Unknown.source().is_none());
+}
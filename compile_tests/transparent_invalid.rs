@@ -0,0 +1,19 @@
+#[derive(Debug, onlyerror::Error)]
+enum Message {
+    #[error(transparent, "I/O error")]
+    Io(std::io::Error),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Fields {
+    #[error(transparent)]
+    Io(std::io::Error, String),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Unit {
+    #[error(transparent)]
+    Unknown,
+}
+
+fn main() {}
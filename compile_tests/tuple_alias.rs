@@ -0,0 +1,25 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("{name} failed at line {line}", name = 0, line = 1)]
+    Named(String, u32),
+
+    #[error("Fetching {0} failed: {url:?}", url = 0)]
+    Mixed(String),
+
+    #[error("{path} returned {} ({status})", "OK", path = 0, status = 1)]
+    WithArgs(String, u16),
+}
+
+fn main() {
+    let err = Error::Named("parse".into(), 42);
+    assert_eq!(err.to_string(), "parse failed at line 42");
+
+    let err = Error::Mixed("https://example.com".into());
+    assert_eq!(
+        err.to_string(),
+        r#"Fetching https://example.com failed: "https://example.com""#,
+    );
+
+    let err = Error::WithArgs("/".into(), 200);
+    assert_eq!(err.to_string(), "/ returned OK (200)");
+}
@@ -0,0 +1,7 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("{name} failed at line {line}", name = 0, line = 2)]
+    Named(String, u32),
+}
+
+fn main() {}
@@ -0,0 +1,48 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("Read failed, caused by {source}")]
+    Read(#[source] std::io::Error),
+
+    #[error("Parsing {0} failed: {source:?}")]
+    Parse(String, #[source] std::num::ParseIntError),
+
+    #[error("Converted from {source}")]
+    Utf8(#[from] std::str::Utf8Error),
+
+    #[error("{source} is not the source here", source = 0)]
+    Renamed(String, #[source] std::io::Error),
+
+    /// Decoding failed: {source}
+    Decode(#[from] std::string::FromUtf8Error),
+}
+
+fn main() {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "disk full");
+    assert_eq!(
+        Error::Read(io).to_string(),
+        "Read failed, caused by disk full",
+    );
+
+    let parse = "x".parse::<u8>().unwrap_err();
+    assert_eq!(
+        Error::Parse("x".into(), parse).to_string(),
+        "Parsing x failed: ParseIntError { kind: InvalidDigit }",
+    );
+
+    let bytes = vec![0xff];
+    let utf8 = std::str::from_utf8(&bytes).unwrap_err();
+    assert_eq!(
+        Error::from(utf8).to_string(),
+        format!("Converted from {utf8}"),
+    );
+
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "ignored");
+    assert_eq!(
+        Error::Renamed("Name".into(), io).to_string(),
+        "Name is not the source here",
+    );
+
+    let utf8 = String::from_utf8(vec![0xff]).unwrap_err();
+    let expected = format!("Decoding failed: {utf8}");
+    assert_eq!(Error::from(utf8).to_string(), expected);
+}
@@ -0,0 +1,52 @@
+use std::error::Error as _;
+use std::io::{Error as IoError, ErrorKind};
+
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("{0} at {1}")]
+    Third(String, u32, #[source] IoError),
+
+    #[error("{1} and {10}")]
+    Eleventh(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, #[source] IoError),
+
+    #[error("Failed to read {1:?}")]
+    First(#[from] IoError, String),
+
+    #[error("Failed to write {1:?} with code {2}")]
+    #[rustfmt::skip]
+    Trailing(#[source] IoError, String, u8,),
+}
+
+fn main() {
+    let err = Error::Third("read".into(), 7, IoError::new(ErrorKind::Other, "third"));
+    assert_eq!(err.to_string(), "read at 7");
+    assert_eq!(err.source().unwrap().to_string(), "third");
+
+    let err = Error::Eleventh(
+        0,
+        1,
+        2,
+        3,
+        4,
+        5,
+        6,
+        7,
+        8,
+        9,
+        IoError::new(ErrorKind::Other, "tenth"),
+    );
+    assert_eq!(err.to_string(), "1 and tenth");
+    assert_eq!(err.source().unwrap().to_string(), "tenth");
+
+    let err = Error::from(IoError::new(ErrorKind::Other, "first"));
+    assert_eq!(err.to_string(), r#"Failed to read """#);
+    assert_eq!(err.source().unwrap().to_string(), "first");
+
+    let err = Error::Trailing(
+        IoError::new(ErrorKind::Other, "trailing"),
+        "out.txt".into(),
+        2,
+    );
+    assert_eq!(err.to_string(), r#"Failed to write "out.txt" with code 2"#);
+    assert_eq!(err.source().unwrap().to_string(), "trailing");
+}
@@ -0,0 +1,16 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    /// I/O error with a retry count
+    Io(#[from] (std::io::Error, u8)),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Source {
+    /// Parse error with a line number
+    Parse {
+        #[source]
+        source: (std::num::ParseIntError, usize),
+    },
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+mod error {
+    #[derive(Debug, onlyerror::Error)]
+    pub enum MyError {
+        /// I/O error
+        Io(#[from] std::io::Error),
+
+        /// Parse error
+        Parse(#[from] std::num::ParseIntError),
+    }
+
+    pub type IoError = MyError;
+}
+
+#[doc(inline)]
+pub use error::IoError;
+
+fn parse(input: &str) -> Result<u32, IoError> {
+    Ok(input.parse()?)
+}
+
+fn main() {
+    let io_err = std::io::Error::new(std::io::ErrorKind::Other, "oops");
+    let err = IoError::from(io_err);
+    assert!(matches!(err, error::MyError::Io(_)));
+    assert_eq!(err.to_string(), "I/O error");
+
+    let err = parse("nope").unwrap_err();
+    assert!(matches!(err, IoError::Parse(_)));
+}
@@ -0,0 +1,31 @@
+#[derive(Debug, onlyerror::Error)]
+enum Ошибка {
+    /// Неизвестно
+    Неизвестно,
+
+    #[error("Файл {путь} не найден")]
+    НеНайден { путь: String },
+
+    #[error("Ввод-вывод: {0}")]
+    ВводВывод(#[from] std::io::Error),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Erreur {
+    #[error("Échec après {durée}s")]
+    Délai { durée: u32 },
+}
+
+fn main() {
+    assert_eq!(Ошибка::Неизвестно.to_string(), "Неизвестно");
+
+    let err = Ошибка::НеНайден {
+        путь: "/etc/конфиг".into(),
+    };
+    assert_eq!(err.to_string(), "Файл /etc/конфиг не найден");
+
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "сбой");
+    assert_eq!(Ошибка::from(io).to_string(), "Ввод-вывод: сбой");
+
+    assert_eq!(Erreur::Délai { durée: 5 }.to_string(), "Échec après 5s");
+}
@@ -0,0 +1,42 @@
+#[derive(Debug, onlyerror::Error)]
+enum Error {
+    #[error("\u{1F600} Failed to read {path}\n\tcheck permissions")]
+    Read { path: String },
+
+    #[error(
+        "Tab\tseparated {0} \u{2014} \x41 \
+             done"
+    )]
+    Tuple(u8),
+
+    #[error(r"Raw \n {0} 🚀")]
+    Raw(u8),
+
+    #[error("Only escapes \"\u{1F680}\"\r\n")]
+    Escapes,
+
+    /// Doc comment with an emoji 🦀, "quotes", a \ backslash, and {0}
+    Doc(u8),
+}
+
+fn main() {
+    let err = Error::Read {
+        path: "a.txt".into(),
+    };
+    assert_eq!(
+        err.to_string(),
+        "\u{1F600} Failed to read a.txt\n\tcheck permissions"
+    );
+
+    assert_eq!(
+        Error::Tuple(3).to_string(),
+        "Tab\tseparated 3 \u{2014} A done"
+    );
+
+    assert_eq!(Error::Raw(1).to_string(), "Raw \\n 1 🚀");
+    assert_eq!(Error::Escapes.to_string(), "Only escapes \"🚀\"");
+    assert_eq!(
+        Error::Doc(2).to_string(),
+        r#"Doc comment with an emoji 🦀, "quotes", a \ backslash, and 2"#,
+    );
+}
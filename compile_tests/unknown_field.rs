@@ -0,0 +1,37 @@
+#[derive(Debug, onlyerror::Error)]
+enum Typo {
+    #[error("Request to {host} failed with {stauts}")]
+    Struct { host: String, status: u16 },
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum OutOfRange {
+    #[error("Expected {0}, found {2}")]
+    Tuple(u8, u8),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Alias {
+    #[error("Fetching {uri} failed", url = 0)]
+    Tuple(String),
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Positional {
+    #[error("Field {1} of {r#type}")]
+    Struct { r#type: String },
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Empty {
+    #[error("Empty {name}")]
+    Struct {},
+}
+
+#[derive(Debug, onlyerror::Error)]
+enum Unit {
+    #[error("missing {typo}")]
+    Foo,
+}
+
+fn main() {}
@@ -1,9 +1,9 @@
-use error_iter::ErrorIter as _;
 use onlyerror::Error;
 use std::process::ExitCode;
 
 /// All of my errors.
 #[derive(Debug, Error)]
+#[error(chain)]
 enum Error {
     /// I/O error with context.
     #[error("I/O error: {ctx}.")]
@@ -40,7 +40,7 @@ fn main() -> ExitCode {
         Ok(_) => ExitCode::SUCCESS,
         Err(err) => {
             eprintln!("Error: {err}");
-            for source in err.sources().skip(1) {
+            for source in err.chain().skip(1) {
                 eprintln!("  Caused by: {source}");
             }
 
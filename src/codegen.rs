@@ -0,0 +1,1199 @@
+use crate::parser::{
+    option_type, rename, snake_case, Error, ErrorSource, Generics, Variant, VariantType,
+};
+use proc_macro::{Group, Span, TokenStream, TokenTree};
+use std::{fmt::Write as _, rc::Rc};
+
+/// Marks an expression generated for a field, as in `__onlyerror_spanned[Variant field](expr)`.
+///
+/// Generated code has no spans of its own, so [`respan_fields`] moves these expressions to the
+/// field they are generated for.
+const SPANNED: &str = "__onlyerror_spanned";
+
+/// Generate the source code for all derived impls.
+///
+/// The AST has already been validated by the parser, so this cannot fail.
+#[allow(clippy::too_many_lines)]
+pub(crate) fn generate(ast: &Error, std_crate: &str, alloc_crate: &str) -> String {
+    let name = &ast.name;
+    let Generics {
+        params,
+        args,
+        where_clause,
+        ..
+    } = &ast.generics;
+    let option = format!("::{std_crate}::option::Option");
+
+    let allow_deprecated = allow_deprecated(ast);
+    let source_arms = ast
+        .variants
+        .iter()
+        .filter_map(|v| {
+            let index = match &v.source {
+                _ if v.transparent => v.fields.keys().next()?,
+                ErrorSource::From(index) | ErrorSource::Source(index) => index,
+                ErrorSource::None => return None,
+            };
+            let path = variant_path(ast, v);
+            let deref = deref(v, index);
+
+            // Transparent variants forward to the source of the inner error.
+            let source = |binding: &str| {
+                if v.transparent || v.forward_source {
+                    format!("::{std_crate}::error::Error::source({deref}{binding})")
+                } else if let Some(inner) = option_type(&v.fields[index]) {
+                    let deref = if v.deref_source || is_smart_pointer(inner) {
+                        "&**"
+                    } else {
+                        ""
+                    };
+
+                    format!(
+                        "{binding}.as_ref().map(|source| \
+                        {deref}source as &(dyn ::{std_crate}::error::Error + 'static))"
+                    )
+                } else {
+                    format!("{option}::Some({deref}{binding})")
+                }
+            };
+
+            Some(match &v.ty {
+                VariantType::Unit => (path, format!("{option}::None")),
+                VariantType::Tuple => {
+                    let fields = (0..v.fields.len())
+                        .map(|i| {
+                            if i.to_string() == **index {
+                                "field,"
+                            } else {
+                                "_,"
+                            }
+                        })
+                        .collect::<String>();
+
+                    (format!("{path}({fields})"), source("field"))
+                }
+                VariantType::Struct => (format!("{path} {{ {index}, ..}}"), source(index)),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // A single source arm doesn't need a `match`, unless it would make the pattern irrefutable.
+    let source_body = match source_arms.as_slice() {
+        [(pattern, expr)] if ast.variants.len() > 1 => {
+            format!("if let {pattern} = self {{ {expr} }} else {{ {option}::None }}")
+        }
+        arms => {
+            let arms = arms
+                .iter()
+                .fold(String::new(), |mut arms, (pattern, expr)| {
+                    let _ = write!(arms, "{pattern} => {expr},");
+                    arms
+                });
+
+            format!("match self {{ {arms} _ => {option}::None, }}")
+        }
+    };
+
+    // `Error::provide` is unstable, so it is only generated on request.
+    let provide_fn = if cfg!(feature = "provide") {
+        provide_fn(ast, std_crate)
+    } else {
+        String::new()
+    };
+
+    let error_impl = if ast.no_error_impl {
+        String::new()
+    } else if ast.no_display {
+        // The `Display` supertrait is required through a projection, which makes the bound
+        // generic over a lifetime. rustc only checks it where the impl is used, so a missing
+        // hand-written impl is reported once, by `AssertDisplay`.
+        let params = match params.strip_prefix('<') {
+            Some(params) => format!("<'__display, {params}"),
+            None => "<'__display>".to_string(),
+        };
+        let where_clause = ast.generics.where_clause_and([format!(
+            "<{name}{args} as Identity<'__display>>::This: ::{std_crate}::fmt::Display"
+        )]);
+
+        format!(
+            r"trait Identity<'a> {{
+                type This: ?::{std_crate}::marker::Sized;
+            }}
+
+            impl<'a, T: ?::{std_crate}::marker::Sized> Identity<'a> for T {{
+                type This = T;
+            }}
+
+            {allow_deprecated}
+            impl{params} ::{std_crate}::error::Error for {name}{args} {where_clause} {{
+                #[inline]
+                fn source(&self) -> {option}<&(dyn ::{std_crate}::error::Error + 'static)> {{
+                    {source_body}
+                }}
+
+                {provide_fn}
+            }}"
+        )
+    } else {
+        format!(
+            r"{allow_deprecated}
+            impl{params} ::{std_crate}::error::Error for {name}{args} {where_clause} {{
+                #[inline]
+                fn source(&self) -> {option}<&(dyn ::{std_crate}::error::Error + 'static)> {{
+                    {source_body}
+                }}
+
+                {provide_fn}
+            }}"
+        )
+    };
+
+    // A missing hand-written impl is reported as `Display`, not as the `Error` supertrait. The
+    // `Error` impl shares the helper traits, so it is generated in the same block.
+    let display_impl = if ast.no_display {
+        format!(
+            r"const _: () = {{
+                trait AssertDisplay: ::{std_crate}::fmt::Display {{}}
+
+                impl{params} AssertDisplay for {name}{args} {where_clause} {{}}
+
+                {error_impl}
+            }};"
+        )
+    } else {
+        let display_matches = if let Some(delegate) = &ast.delegate {
+            format!("self.{delegate}(__formatter)")
+        } else {
+            display_matches(ast, std_crate)
+        };
+
+        format!(
+            r"{allow_deprecated}
+            impl{params} ::{std_crate}::fmt::Display for {name}{args} {where_clause} {{
+                fn fmt(&self, __formatter: &mut ::{std_crate}::fmt::Formatter<'_>) ->
+                    ::{std_crate}::result::Result<(), ::{std_crate}::fmt::Error>
+                {{
+                    {display_matches}
+                }}
+            }}"
+        )
+    };
+    let error_impl = if ast.no_display {
+        String::new()
+    } else {
+        error_impl
+    };
+
+    let from_impls = ast
+        .variants
+        .iter()
+        .filter_map(|v| match &v.source {
+            ErrorSource::From(index) => {
+                let from_ty = &v.fields[index];
+                let body = from_body(ast, v, index, std_crate);
+
+                // The inner type of a boxed field is converted through the `Box` impl.
+                let boxed_impl = v.boxed_from.as_ref().map_or_else(String::new, |inner| {
+                    format!(
+                        r"{allow_deprecated}
+                        impl{params} ::{std_crate}::convert::From<{inner}> for {name}{args}
+                            {where_clause}
+                        {{
+                            #[inline]
+                            fn from(value: {inner}) -> Self {{
+                                <Self as ::{std_crate}::convert::From<{from_ty}>>::from(
+                                    ::{alloc_crate}::boxed::Box::new(value),
+                                )
+                            }}
+                        }}"
+                    )
+                });
+
+                Some(format!(
+                    r"{allow_deprecated}
+                    impl{params} ::{std_crate}::convert::From<{from_ty}> for {name}{args}
+                        {where_clause}
+                    {{
+                        #[inline]
+                        fn from(value: {from_ty}) -> Self {{
+                            {body}
+                        }}
+                    }}
+                    {boxed_impl}"
+                ))
+            }
+            _ => None,
+        })
+        .collect::<String>();
+
+    let predicates_impl = if ast.predicates {
+        predicates_impl(ast, std_crate)
+    } else {
+        String::new()
+    };
+
+    let const_new_impl = if ast.const_new {
+        const_new_impl(ast, std_crate)
+    } else {
+        String::new()
+    };
+
+    let code_impl = if ast.variants.iter().any(|v| v.code.is_some()) {
+        code_impl(ast)
+    } else {
+        String::new()
+    };
+
+    let chain_impl = if ast.chain {
+        format!(
+            r"impl{params} {name}{args} {where_clause} {{
+                /// Iterate over this error and its chain of sources.
+                pub fn chain(&self) ->
+                    impl ::{std_crate}::iter::Iterator<Item = &(dyn ::{std_crate}::error::Error + 'static)>
+                where
+                    Self: 'static,
+                {{
+                    ::{std_crate}::iter::successors(
+                        {option}::Some(self as &(dyn ::{std_crate}::error::Error + 'static)),
+                        |err| ::{std_crate}::error::Error::source(*err),
+                    )
+                }}
+            }}"
+        )
+    } else {
+        String::new()
+    };
+
+    let eq_impl = if ast.eq {
+        eq_impl(ast, std_crate)
+    } else {
+        String::new()
+    };
+
+    let kind_impl = if ast.kind {
+        kind_impl(ast, std_crate)
+    } else {
+        String::new()
+    };
+
+    let debug_impl = if ast.debug_as_display {
+        format!(
+            r"impl{params} ::{std_crate}::fmt::Debug for {name}{args} {where_clause} {{
+                fn fmt(&self, __formatter: &mut ::{std_crate}::fmt::Formatter<'_>) ->
+                    ::{std_crate}::result::Result<(), ::{std_crate}::fmt::Error>
+                {{
+                    ::{std_crate}::fmt::Display::fmt(self, __formatter)
+                }}
+            }}"
+        )
+    } else {
+        String::new()
+    };
+
+    format!(
+        r"
+            {display_impl}
+            {error_impl}
+            {from_impls}
+            {predicates_impl}
+            {const_new_impl}
+            {eq_impl}
+            {debug_impl}
+            {code_impl}
+            {kind_impl}
+            {chain_impl}
+        "
+    )
+}
+
+/// Generate an inherent impl with a `code()` method returning the code of each variant.
+fn code_impl(ast: &Error) -> String {
+    let name = &ast.name;
+    let Generics {
+        params,
+        args,
+        where_clause,
+        ..
+    } = &ast.generics;
+    let allow_deprecated = allow_deprecated(ast);
+
+    let arms = ast
+        .variants
+        .iter()
+        .filter_map(|v| Some(format!("{} {{ .. }} => {},", variant_path(ast, v), v.code?)))
+        .collect::<String>();
+
+    format!(
+        r"impl{params} {name}{args} {where_clause} {{
+            /// The error code from `#[error(code = N)]`.
+            #[must_use]
+            {allow_deprecated}
+            pub fn code(&self) -> u32 {{
+                match self {{
+                    {arms}
+                }}
+            }}
+        }}"
+    )
+}
+
+/// Generate a `PartialEq` impl comparing variants and their fields, except for sources and
+/// backtraces.
+fn eq_impl(ast: &Error, std_crate: &str) -> String {
+    let name = &ast.name;
+    let Generics { params, args, .. } = &ast.generics;
+    // Fields with generic types are compared, so the parameters must be comparable too.
+    let where_clause = ast
+        .generics
+        .where_clause_with(&format!("::{std_crate}::cmp::PartialEq"));
+    let allow_deprecated = allow_deprecated(ast);
+
+    let arms = ast.variants.iter().fold(String::new(), |mut arms, v| {
+        let path = variant_path(ast, v);
+        let mut keys = v
+            .fields
+            .keys()
+            .filter(|key| v.source.as_ref() != Some(key) && v.backtrace.as_ref() != Some(key))
+            .collect::<Vec<_>>();
+        keys.sort();
+
+        let pattern = |side: &str| {
+            let fields = keys
+                .iter()
+                .enumerate()
+                .fold(String::new(), |mut fields, (i, key)| {
+                    let _ = write!(fields, "{key}: __{side}_{i},");
+                    fields
+                });
+
+            format!("{path} {{ {fields} .. }}")
+        };
+        let body = (0..keys.len())
+            .map(|i| format!("__self_{i} == __other_{i}"))
+            .chain(keys.is_empty().then(|| "true".to_string()))
+            .collect::<Vec<_>>()
+            .join(" && ");
+
+        let _ = write!(
+            arms,
+            "({}, {}) => {body},",
+            pattern("self"),
+            pattern("other")
+        );
+        arms
+    });
+
+    format!(
+        r"{allow_deprecated}
+        impl{params} ::{std_crate}::cmp::PartialEq for {name}{args} {where_clause} {{
+            #[allow(unreachable_patterns)]
+            fn eq(&self, other: &Self) -> bool {{
+                match (self, other) {{
+                    {arms}
+                    _ => false,
+                }}
+            }}
+        }}"
+    )
+}
+
+/// Generate a `<Name>Kind` enum with a unit variant for each variant, and a `kind()` method.
+fn kind_impl(ast: &Error, std_crate: &str) -> String {
+    let name = &ast.name;
+    let vis = &ast.vis;
+    let Generics {
+        params,
+        args,
+        where_clause,
+        ..
+    } = &ast.generics;
+    let kind = format!("{name}Kind");
+
+    let allow_deprecated = allow_deprecated(ast);
+    let rule = ast.rename_all.as_deref().unwrap_or("PascalCase");
+    let (variants, arms, names) = ast.variants.iter().fold(
+        (String::new(), String::new(), String::new()),
+        |(mut variants, mut arms, mut names), v| {
+            let variant = &v.name;
+            let path = variant_path(ast, v);
+            let _ = write!(
+                variants,
+                "/// The kind of [`{name}::{variant}`].\n{variant},"
+            );
+            let _ = write!(arms, "{path} {{ .. }} => {kind}::{variant},");
+            let _ = write!(names, "Self::{variant} => {:?},", rename(variant, rule));
+            (variants, arms, names)
+        },
+    );
+
+    format!(
+        r"/// The kind of [`{name}`], without any fields.
+        #[derive(
+            ::{std_crate}::fmt::Debug,
+            ::{std_crate}::clone::Clone,
+            ::{std_crate}::marker::Copy,
+            ::{std_crate}::cmp::PartialEq,
+            ::{std_crate}::cmp::Eq,
+        )]
+        #[repr(u8)]
+        {vis} enum {kind} {{
+            {variants}
+        }}
+
+        impl {kind} {{
+            /// The name of this kind, which is stable for use in logs and wire formats.
+            #[must_use]
+            pub const fn as_str(&self) -> &'static str {{
+                match self {{
+                    {names}
+                }}
+            }}
+        }}
+
+        impl{params} {name}{args} {where_clause} {{
+            /// The kind of this error, without any fields.
+            #[must_use]
+            {allow_deprecated}
+            pub fn kind(&self) -> {kind} {{
+                match self {{
+                    {arms}
+                }}
+            }}
+        }}"
+    )
+}
+
+/// Replace the expressions marked with [`SPANNED`] in the generated code, so errors in them are
+/// reported at the field they are generated for instead of at the derive.
+pub(crate) fn respan_fields(stream: TokenStream, ast: &Error) -> TokenStream {
+    let mut output = TokenStream::new();
+    let mut trees = stream.into_iter();
+
+    while let Some(tree) = trees.next() {
+        match tree {
+            TokenTree::Ident(ident) if ident.to_string() == SPANNED => {
+                let (Some(TokenTree::Group(field)), Some(TokenTree::Group(expr))) =
+                    (trees.next(), trees.next())
+                else {
+                    unreachable!("Marked expressions are generated with a field and a body");
+                };
+                let field = field
+                    .stream()
+                    .into_iter()
+                    .map(|tree| tree.to_string())
+                    .collect::<Vec<_>>();
+                let span = ast
+                    .variants
+                    .iter()
+                    .find(|v| v.name == field[0])
+                    .and_then(|v| v.field_spans.get(field[1].as_str()))
+                    .copied()
+                    .unwrap_or_else(Span::call_site);
+
+                output.extend(respan(expr.stream(), span));
+            }
+            TokenTree::Group(group) => {
+                let mut respanned =
+                    Group::new(group.delimiter(), respan_fields(group.stream(), ast));
+                respanned.set_span(group.span());
+                output.extend([TokenTree::Group(respanned)]);
+            }
+            tree => output.extend([tree]),
+        }
+    }
+
+    output
+}
+
+/// Set the span of every token in `stream`.
+fn respan(stream: TokenStream, span: Span) -> TokenStream {
+    stream
+        .into_iter()
+        .map(|tree| match tree {
+            TokenTree::Group(group) => {
+                let mut respanned = Group::new(group.delimiter(), respan(group.stream(), span));
+                respanned.set_span(span);
+                TokenTree::Group(respanned)
+            }
+            mut tree => {
+                tree.set_span(span);
+                tree
+            }
+        })
+        .collect()
+}
+
+/// Construct a variant from the `value` of its `#[from]` field.
+fn from_body(ast: &Error, v: &Variant, index: &str, std_crate: &str) -> String {
+    let path = variant_path(ast, v);
+
+    // A closure builds the whole variant from the source.
+    if let Some(with) = &v.from_with {
+        return format!("({with})(value)");
+    }
+
+    // Backtraces are captured, and other fields are filled with defaults.
+    let value = |key: &str| {
+        if key == index {
+            "value".to_string()
+        } else if v.backtrace.as_deref() == Some(key) {
+            format!("::{std_crate}::backtrace::Backtrace::capture()")
+        } else {
+            // A missing `Default` impl is reported at the field type.
+            let ty = &v.fields[key];
+            let name = &v.name;
+            format!("{SPANNED}[{name} {key}](<{ty} as ::{std_crate}::default::Default>::default())")
+        }
+    };
+
+    if v.ty == VariantType::Tuple {
+        let fields = (0..v.fields.len())
+            .map(|i| value(&i.to_string()))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(r"{path}({fields})")
+    } else {
+        let mut keys = v.fields.keys().collect::<Vec<_>>();
+        keys.sort();
+        let fields = keys
+            .into_iter()
+            .map(|key| format!("{key}: {}", value(key)))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(r"{path} {{ {fields} }}")
+    }
+}
+
+/// Generate an inherent impl with a `const fn from_<variant>()` constructor for each `#[from]`
+/// variant.
+///
+/// Backtraces and defaults cannot be created in a `const fn`, so only variants with a single field
+/// get a constructor.
+fn const_new_impl(ast: &Error, std_crate: &str) -> String {
+    let name = &ast.name;
+    let Generics {
+        params,
+        args,
+        where_clause,
+        ..
+    } = &ast.generics;
+    let allow_deprecated = allow_deprecated(ast);
+
+    let methods = ast
+        .variants
+        .iter()
+        .filter_map(|v| match &v.source {
+            ErrorSource::From(index) if v.fields.len() == 1 && v.from_with.is_none() => {
+                Some((v, index))
+            }
+            _ => None,
+        })
+        .fold(String::new(), |mut methods, (v, index)| {
+            let variant = &v.name;
+            let method = format!("from_{}", snake_case(variant));
+            let from_ty = &v.fields[index];
+            let body = from_body(ast, v, index, std_crate);
+
+            let _ = write!(
+                methods,
+                r"/// Create a [`{name}::{variant}`] variant in const contexts.
+                #[must_use]
+                {allow_deprecated}
+                pub const fn {method}(value: {from_ty}) -> Self {{
+                    {body}
+                }}"
+            );
+            methods
+        });
+
+    format!("impl{params} {name}{args} {where_clause} {{ {methods} }}")
+}
+
+/// Generate an inherent impl with an `is_<variant>()` method for each variant.
+fn predicates_impl(ast: &Error, std_crate: &str) -> String {
+    let name = &ast.name;
+    let Generics {
+        params,
+        args,
+        where_clause,
+        ..
+    } = &ast.generics;
+    let allow_deprecated = allow_deprecated(ast);
+
+    let methods = ast.variants.iter().fold(String::new(), |mut methods, v| {
+        let variant = &v.name;
+        let method = format!("is_{}", snake_case(variant));
+
+        let _ = write!(
+            methods,
+            r"/// Returns `true` if the error is a [`{name}::{variant}`] variant.
+                #[must_use]
+                {allow_deprecated}
+                pub fn {method}(&self) -> bool {{
+                    ::{std_crate}::matches!(self, Self::{variant} {{ .. }})
+                }}"
+        );
+        methods
+    });
+
+    format!("impl{params} {name}{args} {where_clause} {{ {methods} }}")
+}
+
+/// Generate `Error::provide` for fields marked with `#[provide]` or `#[backtrace]`.
+fn provide_fn(ast: &Error, std_crate: &str) -> String {
+    let arms = ast
+        .variants
+        .iter()
+        .filter_map(|v| {
+            let provided = v.provide.iter().chain(&v.backtrace).collect::<Vec<_>>();
+            if provided.is_empty() {
+                return None;
+            }
+
+            let path = variant_path(ast, v);
+            let binding = |key: &str| {
+                if v.ty == VariantType::Tuple {
+                    format!("field_{key}")
+                } else {
+                    key.to_string()
+                }
+            };
+            let pattern = if v.ty == VariantType::Tuple {
+                let fields = (0..v.fields.len())
+                    .map(|i| {
+                        let key = i.to_string();
+                        if provided.iter().any(|field| ***field == *key) {
+                            binding(&key)
+                        } else {
+                            "_".to_string()
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!("{path}({fields})")
+            } else {
+                let fields = provided.iter().fold(String::new(), |mut fields, key| {
+                    let _ = write!(fields, "{key}, ");
+                    fields
+                });
+
+                format!("{path} {{ {fields}.. }}")
+            };
+            let body = provided.iter().fold(String::new(), |mut body, key| {
+                let ty = &v.fields[*key];
+                let binding = binding(key);
+                let _ = write!(body, "__request.provide_ref::<{ty}>({binding});");
+                body
+            });
+
+            Some(format!("{pattern} => {{ {body} }}"))
+        })
+        .collect::<String>();
+
+    if arms.is_empty() {
+        return String::new();
+    }
+
+    format!(
+        r"#[allow(unreachable_patterns)]
+        fn provide<'__request>(
+            &'__request self,
+            __request: &mut ::{std_crate}::error::Request<'__request>,
+        ) {{
+            match self {{
+                {arms}
+                _ => {{}}
+            }}
+        }}"
+    )
+}
+
+/// Generate the `match` expression for the derived `Display` impl.
+#[allow(clippy::too_many_lines)]
+fn display_matches(ast: &Error, std_crate: &str) -> String {
+    // The prefix is literal text, so braces are escaped for `format_args!`.
+    let prefix = ast
+        .prefix
+        .as_deref()
+        .unwrap_or_default()
+        .replace('{', "{{")
+        .replace('}', "}}");
+
+    let display_matches = ast
+        .variants
+        .iter()
+        .filter_map(|v| {
+            let path = variant_path(ast, v);
+            if v.transparent {
+                let index = v.fields.keys().next()?;
+                let deref = deref(v, index);
+                let display =
+                    format!("::{std_crate}::fmt::Display::fmt({deref}field, __formatter)");
+
+                return Some(if v.ty == VariantType::Tuple {
+                    format!("{path}(field) => {display},")
+                } else {
+                    format!("{path} {{ {index}: field }} => {display},")
+                });
+            }
+
+            if v.skip_display {
+                let method = format!("fmt_{}", snake_case(&v.name));
+                return Some(format!("{path} {{ .. }} => self.{method}(__formatter),"));
+            }
+
+            // The formatting function takes each field by reference, followed by the formatter.
+            if let Some(fmt) = &v.fmt {
+                let fields = v
+                    .field_order
+                    .iter()
+                    .fold(String::new(), |mut fields, field| {
+                        if v.ty == VariantType::Tuple {
+                            let _ = write!(fields, "field_{field},");
+                        } else {
+                            let _ = write!(fields, "{field},");
+                        }
+                        fields
+                    });
+                let pattern = match &v.ty {
+                    VariantType::Unit => path,
+                    VariantType::Tuple => format!("{path}({fields})"),
+                    VariantType::Struct => format!("{path} {{ {fields} }}"),
+                };
+
+                return Some(format!("{pattern} => {fmt}({fields} __formatter),"));
+            }
+
+            // Variants without a message can display their source instead.
+            let index = v
+                .source
+                .as_ref()
+                .filter(|_| ast.chain_source && !v.optional_source);
+            if let (None, Some(index)) = (&v.display, index) {
+                let deref = deref(v, index);
+                let display = format!("{prefix}{{}}");
+                let format_args = format!("::{std_crate}::format_args!({display:?}, {deref}field)");
+
+                return Some(format!(
+                    "{path} {{ {index}: field, .. }} => __formatter.write_fmt({format_args}),"
+                ));
+            }
+
+            // The source is appended to messages that don't already display it. Optional sources
+            // are only appended when they are present.
+            let mut display_fields = v.display_fields.clone();
+            let mut display_args = v.display_args.clone();
+            let mut suffix = "";
+            let mut optional_source = None;
+            if let Some(index) = v.source.as_ref().filter(|_| ast.append_source) {
+                let binding: Rc<str> = if v.ty == VariantType::Tuple {
+                    Rc::from(format!("field_{index}"))
+                } else {
+                    index.clone()
+                };
+                if !display_fields.contains(&binding) {
+                    display_fields.push(binding.clone());
+                    if v.optional_source {
+                        optional_source = Some(binding);
+                    } else {
+                        let deref = deref(v, index);
+                        if !display_args.is_empty() {
+                            display_args.push_str(", ");
+                        }
+                        let _ = write!(display_args, "__source = {deref}{binding}");
+                        suffix = ": {__source}";
+                    }
+                }
+            }
+
+            // Macro messages are expanded by `format_args!`, so the prefix is concatenated.
+            let message = v.display.as_ref()?;
+            let display = match (v.display_macro, prefix.is_empty() && suffix.is_empty()) {
+                (true, true) => message.clone(),
+                (true, false) => {
+                    format!("::{std_crate}::concat!({prefix:?}, {message}, {suffix:?})")
+                }
+                (false, _) => format!("{:?}", format!("{prefix}{message}{suffix}")),
+            };
+            let format_args = format!("::{std_crate}::format_args!({display}, {display_args})");
+
+            // Messages without placeholders are written directly, skipping the formatting
+            // machinery.
+            let text = format!("{prefix}{message}{suffix}");
+            let write = if !v.display_macro && display_args.is_empty() && !text.contains(['{', '}'])
+            {
+                format!("__formatter.write_str({text:?})")
+            } else {
+                format!("__formatter.write_fmt({format_args})")
+            };
+            let write = if let Some(binding) = optional_source {
+                format!(
+                    r#"{{
+                        {write}?;
+                        if let ::{std_crate}::option::Option::Some(__source) = {binding} {{
+                            __formatter.write_fmt(::{std_crate}::format_args!(": {{}}", __source))
+                        }} else {{
+                            ::{std_crate}::result::Result::Ok(())
+                        }}
+                    }}"#
+                )
+            } else {
+                write
+            };
+
+            Some(match &v.ty {
+                VariantType::Unit => format!("{path} => {write},"),
+                VariantType::Tuple => {
+                    let fields = (0..v.fields.len()).fold(String::new(), |mut fields, i| {
+                        if display_fields.contains(&Rc::from(format!("field_{i}"))) {
+                            let _ = write!(fields, "field_{i},");
+                        } else {
+                            let _ = fields.write_str("_,");
+                        }
+                        fields
+                    });
+                    format!("{path}({fields}) => {write},")
+                }
+                VariantType::Struct => {
+                    let display_fields =
+                        display_fields
+                            .iter()
+                            .fold(String::new(), |mut fields, field| {
+                                let _ = write!(fields, "{field},");
+                                fields
+                            });
+
+                    format!("{path} {{ {display_fields} .. }} => {write},")
+                }
+            })
+        })
+        .collect::<String>();
+
+    if display_matches.is_empty() {
+        format!("::{std_crate}::result::Result::Ok(())")
+    } else {
+        format!("match self {{ {display_matches} }}")
+    }
+}
+
+/// Allow generated code to reference deprecated variants, which every impl may do through its
+/// match arms or constructors.
+fn allow_deprecated(ast: &Error) -> &'static str {
+    if ast.variants.iter().any(|v| v.deprecated) {
+        "#[allow(deprecated)]"
+    } else {
+        ""
+    }
+}
+
+/// The path used to construct and match a variant, which is just `Self` for structs.
+fn variant_path(ast: &Error, v: &Variant) -> String {
+    if ast.is_struct {
+        "Self".to_string()
+    } else {
+        format!("Self::{}", v.name)
+    }
+}
+
+/// The dereference needed to turn a reference to the source field into `&dyn Error`.
+fn deref(v: &Variant, index: &str) -> &'static str {
+    if v.deref_source || is_smart_pointer(&v.fields[index]) {
+        "&**"
+    } else {
+        ""
+    }
+}
+
+/// Boxed, `Arc`, and `Rc` sources are dereferenced so `source()` returns the inner error instead of
+/// the pointer.
+fn is_smart_pointer(ty: &str) -> bool {
+    [
+        "Box<",
+        "std::boxed::Box<",
+        "alloc::boxed::Box<",
+        "Arc<",
+        "std::sync::Arc<",
+        "alloc::sync::Arc<",
+        "Rc<",
+        "std::rc::Rc<",
+        "alloc::rc::Rc<",
+    ]
+    .iter()
+    .any(|prefix| ty.trim_start_matches("::").starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Strip whitespace so assertions don't depend on the code templates' indentation.
+    fn compact(code: &str) -> String {
+        code.split_whitespace().collect()
+    }
+
+    fn variant(name: &str, ty: VariantType) -> Variant {
+        Variant {
+            name: name.to_string(),
+            ty,
+            fields: HashMap::new(),
+            field_spans: HashMap::new(),
+            display: None,
+            display_fields: vec![],
+            display_args: String::new(),
+            display_macro: false,
+            source: ErrorSource::None,
+            from_span: None,
+            deref_source: false,
+            optional_source: false,
+            boxed_from: None,
+            from_with: None,
+            transparent: false,
+            forward_source: false,
+            skip_display: false,
+            fmt: None,
+            field_order: vec![],
+            backtrace: None,
+            provide: vec![],
+            code: None,
+            deprecated: false,
+        }
+    }
+
+    fn error(variants: Vec<Variant>) -> Error {
+        Error {
+            name: "Error".to_string(),
+            vis: String::new(),
+            generics: Generics::default(),
+            variants,
+            no_display: false,
+            no_error_impl: false,
+            delegate: None,
+            prefix: None,
+            predicates: false,
+            const_new: false,
+            chain_source: false,
+            eq: false,
+            chain: false,
+            append_source: false,
+            debug_as_display: false,
+            kind: false,
+            rename_all: None,
+            is_struct: false,
+        }
+    }
+
+    #[test]
+    fn test_unit_display() {
+        let mut unit = variant("NotFound", VariantType::Unit);
+        unit.display = Some("Not found".to_string());
+        let code = compact(&generate(&error(vec![unit]), "std", "std"));
+
+        assert!(code.contains(&compact(
+            r#"Self::NotFound => __formatter.write_str("Not found"),"#
+        )));
+        assert!(code.contains(&compact("_ => ::std::option::Option::None,")));
+    }
+
+    #[test]
+    fn test_placeholder_display() {
+        let mut code = variant("Code", VariantType::Tuple);
+        code.fields.insert("0".into(), "u16".to_string());
+        code.display = Some("Code {field_0}".to_string());
+        code.display_fields = vec!["field_0".into()];
+        let mut escaped = variant("Escaped", VariantType::Unit);
+        escaped.display = Some("{{}}".to_string());
+        let code = compact(&generate(&error(vec![code, escaped]), "std", "std"));
+
+        assert!(code.contains(&compact(
+            r#"Self::Code(field_0,) => __formatter.write_fmt(::std::format_args!("Code {field_0}", )),"#
+        )));
+        assert!(code.contains(&compact(
+            r#"Self::Escaped => __formatter.write_fmt(::std::format_args!("{{}}", )),"#
+        )));
+    }
+
+    #[test]
+    fn test_fmt_display() {
+        let mut range = variant("Range", VariantType::Tuple);
+        range.fields.insert("0".into(), "u32".to_string());
+        range.fields.insert("1".into(), "u32".to_string());
+        range.field_order = vec!["0".into(), "1".into()];
+        range.fmt = Some("format::range".to_string());
+        let mut unit = variant("Unit", VariantType::Unit);
+        unit.fmt = Some("unit".to_string());
+        let code = compact(&generate(&error(vec![range, unit]), "std", "std"));
+
+        assert!(code.contains(&compact(
+            "Self::Range(field_0,field_1,) => format::range(field_0,field_1, __formatter),"
+        )));
+        assert!(code.contains(&compact("Self::Unit => unit( __formatter),")));
+    }
+
+    #[test]
+    fn test_boxed_source() {
+        let mut nested = variant("Nested", VariantType::Tuple);
+        nested.fields.insert("0".into(), "Box<Error>".to_string());
+        nested.source = ErrorSource::From("0".into());
+        let mut ast = error(vec![nested]);
+        ast.no_display = true;
+        let code = compact(&generate(&ast, "core", "alloc"));
+
+        assert!(code.contains(&compact(
+            "Self::Nested(field,) => ::core::option::Option::Some(&**field),"
+        )));
+        assert!(code.contains(&compact("impl ::core::convert::From<Box<Error>> for Error")));
+        assert!(code.contains(&compact("#[inline] fn source(&self)")));
+        assert!(!code.contains(&compact("impl ::core::fmt::Display for Error")));
+        assert!(code.contains(&compact("impl AssertDisplay for Error {}")));
+        assert!(code.contains(&compact(
+            "impl<'__display> ::core::error::Error for Error \
+            where <Error as Identity<'__display>>::This: ::core::fmt::Display"
+        )));
+    }
+
+    #[test]
+    fn test_from_with() {
+        let mut io = variant("Io", VariantType::Struct);
+        io.fields
+            .insert("source".into(), "std::io::Error".to_string());
+        io.fields.insert("path".into(), "PathBuf".to_string());
+        io.source = ErrorSource::From("source".into());
+        io.from_with = Some("|source| Self::Io { source, path: PathBuf::new() }".to_string());
+        let mut ast = error(vec![io]);
+        ast.no_display = true;
+        ast.const_new = true;
+        let code = compact(&generate(&ast, "std", "std"));
+
+        assert!(code.contains(&compact(
+            "fn from(value: std::io::Error) -> Self { \
+                (|source| Self::Io { source, path: PathBuf::new() })(value) \
+            }"
+        )));
+        assert!(!code.contains("from_io"));
+    }
+
+    #[test]
+    fn test_no_error_impl() {
+        let mut ast = error(vec![variant("Unknown", VariantType::Unit)]);
+        ast.no_error_impl = true;
+        let code = compact(&generate(&ast, "std", "std"));
+
+        assert!(!code.contains("::std::error::Error for"));
+        assert!(code.contains(&compact("impl ::std::fmt::Display for Error")));
+    }
+
+    #[test]
+    fn test_single_source() {
+        let mut io = variant("Io", VariantType::Struct);
+        io.fields
+            .insert("source".into(), "std::io::Error".to_string());
+        io.source = ErrorSource::Source("source".into());
+        let mut ast = error(vec![io, variant("Unknown", VariantType::Unit)]);
+        ast.no_display = true;
+        let code = compact(&generate(&ast, "std", "std"));
+
+        assert!(code.contains(&compact(
+            "if let Self::Io { source, ..} = self { ::std::option::Option::Some(source) } \
+            else { ::std::option::Option::None }"
+        )));
+        assert!(!code.contains("match"));
+    }
+
+    #[test]
+    fn test_default_from() {
+        let mut io = variant("Io", VariantType::Struct);
+        io.fields
+            .insert("source".into(), "std::io::Error".to_string());
+        io.fields.insert("ctx".into(), "String".to_string());
+        io.source = ErrorSource::From("source".into());
+        let mut ast = error(vec![io]);
+        ast.no_display = true;
+        let code = compact(&generate(&ast, "std", "std"));
+
+        assert!(code.contains(&compact(
+            "Self::Io { ctx: __onlyerror_spanned[Io ctx](\
+            <String as ::std::default::Default>::default()), source: value }"
+        )));
+    }
+
+    #[test]
+    fn test_boxed_from() {
+        let mut big = variant("Big", VariantType::Tuple);
+        big.fields.insert("0".into(), "Box<HugeError>".to_string());
+        big.source = ErrorSource::From("0".into());
+        big.boxed_from = Some("HugeError".to_string());
+        let mut ast = error(vec![big]);
+        ast.no_display = true;
+        let code = compact(&generate(&ast, "core", "alloc"));
+
+        assert!(code.contains(&compact(
+            "impl ::core::convert::From<HugeError> for Error { #[inline] \
+            fn from(value: HugeError) -> Self { \
+            <Self as ::core::convert::From<Box<HugeError>>>::from(\
+            ::alloc::boxed::Box::new(value),) } }"
+        )));
+    }
+
+    #[test]
+    fn test_eq_ignores_source() {
+        let mut io = variant("Io", VariantType::Struct);
+        io.fields
+            .insert("source".into(), "std::io::Error".to_string());
+        io.fields.insert("path".into(), "String".to_string());
+        io.source = ErrorSource::Source("source".into());
+        let mut ast = error(vec![io]);
+        ast.no_display = true;
+        ast.eq = true;
+        let code = compact(&generate(&ast, "std", "std"));
+
+        assert!(code.contains(&compact(
+            "(Self::Io { path: __self_0, .. }, Self::Io { path: __other_0, .. }) => \
+            __self_0 == __other_0,"
+        )));
+    }
+
+    #[test]
+    fn test_generics() {
+        let mut ast = error(vec![]);
+        ast.generics = Generics {
+            params: "<T: Debug>".to_string(),
+            args: "<T>".to_string(),
+            where_clause: String::new(),
+            types: vec!["T".to_string()],
+        };
+        let code = compact(&generate(&ast, "std", "std"));
+
+        assert!(code.contains(&compact("impl<T: Debug> ::std::error::Error for Error<T>")));
+        assert!(code.contains(&compact("::std::result::Result::Ok(())")));
+    }
+
+    #[test]
+    fn test_generic_from() {
+        let mut parse = variant("Parse", VariantType::Tuple);
+        parse.fields.insert("0".into(), "nom::Err<E>".to_string());
+        parse.source = ErrorSource::From("0".into());
+        let mut ast = error(vec![parse]);
+        ast.no_display = true;
+        ast.generics = Generics {
+            params: "<E>".to_string(),
+            args: "<E>".to_string(),
+            where_clause: "where E: Debug".to_string(),
+            types: vec!["E".to_string()],
+        };
+        let code = compact(&generate(&ast, "std", "std"));
+
+        assert!(code.contains(&compact(
+            "impl<E> ::std::convert::From<nom::Err<E>> for Error<E> where E: Debug"
+        )));
+
+        ast.eq = true;
+        let code = compact(&generate(&ast, "std", "std"));
+        assert!(code.contains(&compact(
+            "impl<E> ::std::cmp::PartialEq for Error<E> where E: Debug, E: ::std::cmp::PartialEq"
+        )));
+    }
+}
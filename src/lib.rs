@@ -41,10 +41,157 @@
 //!
 //! - The macro derives an implementation for the `Error` trait.
 //! - `Display` is derived using the `#[error("...")]` attributes with a fallback to doc comments.
-//! - `From` is derived for each `#[from]` or `#[source]` attribute.
+//! - `From` is derived for each `#[from]` or `#[source(from)]` attribute. Other fields of the
+//!   variant are filled with `Default::default()`, as in `Parse(#[from] ParseIntError, String)`.
+//! - Generic parameters and `where` clauses on the enum, including const generics, are copied to
+//!   each generated impl.
+//! - Variants and fields gated with `#[cfg(...)]` are removed before the macro runs, and
+//!   `#[cfg_attr(...)]` is expanded, so feature-gated variants like
+//!   `#[cfg(feature = "std")] Io(#[from] std::io::Error)` work in `no_std` crates.
+//! - `source()` dereferences `Box`, `Arc`, and `Rc` fields, returning the inner error rather than
+//!   the pointer. This allows recursive errors like `Nested(#[from] Box<Self>)`, and cloneable
+//!   errors with `#[derive(Clone)]` and sources like `Arc<dyn Error + Send + Sync>`. Other smart
+//!   pointers and type aliases can be dereferenced with `#[source(deref)]` or `#[from(deref)]`.
+//! - `#[source]` can be used on `Option` fields for causes that may be absent, as in
+//!   `#[source] cause: Option<Box<dyn Error + Send + Sync>>`. `source()` returns `None` when the
+//!   field is `None`.
+//! - `#[from(boxed)]` on a `Box<T>` field also derives `From<T>`, which boxes the value. This keeps
+//!   the enum small with variants like `Big(#[from(boxed)] Box<HugeError>)`, while callers can
+//!   still convert from `HugeError` with `?`.
+//! - `#[from(with = ...)]` takes a closure that builds the variant from its source, for variants
+//!   with fields that have no default, as in
+//!   `#[from(with = |source| Self::Io { source, path: PathBuf::new() })] source: io::Error`.
 //!
 //! Error messages in `#[error("...")]` can reference enum variant fields by name (for struct-like
-//! variants) or by number (for tuple-like variants) using the [`std::fmt`] machinery.
+//! variants) or by number (for tuple-like variants) using the [`std::fmt`] machinery. Struct-like
+//! variant fields can also be referenced by number in declaration order, unless the message has
+//! explicit positional arguments. Braces are escaped by doubling them, so `{{count}}` is displayed
+//! as the literal text `{count}`. Long messages can be split into adjacent string literals, as in
+//! `#[error("part one, " "part two")]`.
+//! Fields with raw identifiers like `r#type` are referenced without the prefix, as in `{type}`.
+//! Dynamic widths and precisions can reference fields too, as in `{value:>width$}` or `{0:.1$}`.
+//! References to fields that do not exist are reported by the macro, along with the valid names.
+//!
+//! Messages can also be written with `concat!` or `include_str!`, as in
+//! `#[error(include_str!("messages/timeout.txt"), .0)]`, which is useful for keeping message text
+//! in separate files. The text of these messages is not known to the macro, so fields cannot be
+//! referenced inline. Use positional placeholders with format arguments instead.
+//!
+//! Additional format arguments may follow the message, as in `#[error("{} items", count())]`.
+//! These expressions are passed verbatim to `write!`, so each one is evaluated exactly once per
+//! call to `Display::fmt`, no matter how many times the message references it. Nothing is cached
+//! between calls.
+//!
+//! Format arguments can use struct-like variant fields by name, where each field is bound by
+//! reference. `self.field` is also accepted as a shorthand for the dereferenced field, as in
+//! `#[error("mode: {}", if self.verbose { "verbose" } else { "quiet" })]`. Named arguments can be
+//! mixed with inline field references, as in `#[error("{code:0width$}", width = self.pad)]`, and
+//! take precedence over fields with the same name. Each name can only be given once.
+//! Tuple-like variant fields are accessed the same way with `self.0`. An argument starting with
+//! `.field` or `.0` is shorthand for `self.field` or `self.0`, so derived values can be formatted
+//! with method calls like `#[error("len={}", .0.len())]`.
+//!
+//! Tuple-like variant fields can be given readable names with `name = index` arguments, as in
+//! `#[error("Fetching {url} failed", url = 0)]`. A field can be referenced by both its number and
+//! its name in the same message, and indices past the last field are rejected. The `#[from]` or
+//! `#[source]` field of a tuple-like variant is implicitly named `source`, so
+//! `#[error("Caused by {source}")]` works like it does for struct-like variants.
+//!
+//! An empty message like `#[error("")]` is allowed and writes nothing, which is useful when the
+//! source error is displayed elsewhere. Empty doc comments are treated as a missing message.
+//!
+//! Variants with empty bodies like `Foo {}` and `Foo()` behave like unit variants, which is
+//! convenient for enums generated by other macros.
+//!
+//! Multi-line doc comments are joined with spaces, and runs of whitespace are collapsed into a
+//! single space. Adding `#[error(multiline_docs)]` to the enum joins them with newlines instead,
+//! for messages meant to be printed as a block.
+//!
+//! `#[error(transparent)]` forwards both `Display` and `source()` to the only field of a variant,
+//! as in `#[error(transparent)] Io(#[from] std::io::Error)`. The wrapped error is not reported as
+//! a source itself, so it is only displayed once in an error chain.
+//! The same applies to `source()` for a `#[from]` field displayed with `#[error("{0}")]`, as in
+//! `#[error("{0}")] Config(#[from] ConfigError)`, although the message still has the enum's prefix.
+//!
+//! `#[error(prefix = "myapp: ")]` on the enum writes the prefix before the message of every
+//! variant, including messages from doc comments. Transparent variants are displayed without the
+//! prefix.
+//!
+//! A message on the enum like `#[error("error in {variant}")]` is the default message for variants
+//! that have neither a message nor a doc comment. `{variant}` is replaced with the variant name,
+//! and no other fields can be referenced, since each variant has different fields.
+//! Without `{variant}`, as in `#[error("operation failed")]`, the message is displayed for every
+//! variant, and doc comments on the variants are only documentation. Variants cannot have their
+//! own messages in this case.
+//!
+//! `#[error(predicates)]` on the enum generates an `is_<variant>()` method for each variant, with
+//! the variant name converted to `snake_case`, as in `err.is_not_found()`. Like the other generated
+//! accessors and constructors, these methods are `#[must_use]`.
+//!
+//! `#[error(const_new)]` on the enum generates a `const fn from_<variant>()` constructor for each
+//! `#[from]` variant with a single field, as in `const ERR: Error = Error::from_device(Code(3));`.
+//! This is useful where `From` cannot be called, since trait methods are not `const`.
+//!
+//! Instead of per-variant messages, `#[error(delegate = method)]` on the enum derives a `Display`
+//! impl that calls `self.method(f)`. The inherent method takes the same arguments as
+//! `Display::fmt`, so only the formatting logic needs to be written by hand.
+//!
+//! `#[error(chain_source)]` on the enum displays the source error of variants that have neither a
+//! message nor a doc comment, as in thin wrappers like `Io(#[from] std::io::Error)`. Unlike
+//! `#[error(transparent)]`, the source is still returned by `source()`.
+//!
+//! `#[error(append_source)]` on the enum appends `: {source}` to the message of every variant with
+//! a source, so `#[error("failed to load {path}")]` is displayed as
+//! `failed to load config.toml: permission denied`. Messages that already reference the source
+//! field are left unchanged, and optional sources are only appended when they are `Some`.
+//!
+//! `#[error(eq)]` on the enum derives `PartialEq` for comparing errors in tests, as in
+//! `assert_eq!(err, Error::NotFound)`. Source and backtrace fields are ignored, since types like
+//! `std::io::Error` are not comparable. All other fields are compared by value, so type parameters
+//! are required to implement `PartialEq`, like with `#[derive(PartialEq)]`.
+//!
+//! `#[error(debug_as_display)]` on the enum derives `Debug` with the same text as `Display`, which
+//! keeps noisy fields out of logs and `unwrap()` panics. Remove `Debug` from `#[derive(...)]` when
+//! using this option, since the impls would conflict.
+//!
+//! `#[error(kind)]` on the enum generates a companion `<Name>Kind` enum with a unit variant for
+//! each variant, and a `kind()` method returning it, as in `err.kind() == ErrorKind::NotFound`.
+//! The kind enum has the same visibility as the error, is `#[repr(u8)]` for use across FFI
+//! boundaries, and derives `Debug`, `Clone`, `Copy`, `PartialEq`, and `Eq`.
+//!
+//! `as_str()` on a kind returns its name, which is the variant name unless a case convention is
+//! given with `#[error(rename_all = "snake_case")]`. The conventions are named like serde's:
+//! `lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`, `snake_case`, `SCREAMING_SNAKE_CASE`,
+//! `kebab-case`, and `SCREAMING-KEBAB-CASE`. The option only renames kind names, so it requires
+//! `#[error(kind)]`. Generated methods like `is_<variant>()` are always `snake_case`, since they
+//! are Rust identifiers. Variants that end up with the same name, like `IoError` and `IOError` in
+//! `snake_case`, are reported as an error.
+//!
+//! `#[error(chain)]` on the enum generates a `chain()` method that iterates over the error and its
+//! sources, as in `for cause in err.chain().skip(1) { ... }`.
+//!
+//! Variants can be given stable numeric codes with `#[error(code = 404)]`, written as a separate
+//! attribute from the message. This generates a `code(&self) -> u32` method. When any variant has a
+//! code, every variant must have a unique one.
+//!
+//! A single variant can opt out of its derived message with `#[error(skip_display)]`. The derived
+//! `Display` impl calls `self.fmt_<variant>(f)` for it instead, with the variant name converted to
+//! `snake_case`, so `RateLimited` calls a hand-written `fmt_rate_limited` method.
+//!
+//! Formatting that does not fit a message can be moved into a function with
+//! `#[error(fmt = path::to::function)]`. The function is called with a reference to each field in
+//! order, followed by the `Formatter`, as in
+//! `fn list(items: &[String], f: &mut fmt::Formatter<'_>) -> fmt::Result`. Unit variants only pass
+//! the `Formatter`. `fmt` cannot be combined with a message on the same variant.
+//!
+//! `#[no_error_impl]` on the enum skips the `Error` impl, for types that implement `Error` by hand.
+//! `Display`, `From`, and the generated methods are still derived, so this is the counterpart of
+//! `#[no_display]`.
+//!
+//! Structs are derived like an enum with a single variant. The message, `#[from]`, and `#[source]`
+//! attributes are written on the struct and its fields, as in
+//! `#[error("Config file {path:?} is invalid")] struct ConfigError { path: PathBuf }`. Enum-level
+//! options like `#[error(delegate = method)]` are not available for structs.
 //!
 //! It is recommended to use `#[error("...")]` when you need interpolation, otherwise use doc
 //! comments. Doing this will keep implementation details out of your documentation while making
@@ -52,24 +199,44 @@
 //!
 //! # Limitations
 //!
-//! - Only `enum` types are supported by the [`Error`] macro.
-//! - Only inline string interpolations are supported by the derived `Display` impl.
-//! - Either all variants must be given an error message, or `#[no_display]` attribute must be set
-//!   to enum with hand-written `Display` implementation
-//! - `From` impls are only derived for `#[from]` and `#[source]` attributes, not implicitly for any
-//!   field names.
-//! - `Backtrace` is not supported.
-//! - `#[error(transparent)]` is not supported.
+//! - Only `enum` and `struct` types are supported by the [`Error`] macro.
+//! - Either all variants must be given an error message, `#[error(fmt = ...)]`, or
+//!   `#[error(skip_display)]`, or
+//!   `#[no_display]` attribute must be set to enum with hand-written `Display` implementation, or
+//!   `Display` must be delegated with `#[error(delegate = method)]`.
+//! - `From` impls are only derived for `#[from]` and `#[source(from)]` attributes, not implicitly
+//!   for any field names.
+//! - `#[from]` can only be used on variants whose other fields implement `Default`, not counting a
+//!   `#[backtrace]` field, unless the conversion is written with `#[from(with = ...)]`. Use
+//!   `#[source]` for variants that carry additional context without a default.
+//! - `#[from]` and `#[source]` cannot be used with tuple types like `(io::Error, u8)`, since tuples
+//!   do not implement `Error`.
+//! - Sources cannot be borrowed like `&'a io::Error`, since `source()` returns a `'static` error.
+//!   Other fields can borrow with lifetime parameters, as in `Unexpected(&'a str)`.
+//! - There is no catch-all variant or wrapper struct that converts from any error type. A blanket
+//!   `From` impl would conflict with the reflexive `From<T> for T` impl, since the type is an error
+//!   type itself.
+//! - `Backtrace` fields require the `backtrace` feature, and are only provided through
+//!   `Error::provide` with the `provide` feature.
 //!
 //! # Cargo features
 //!
-//! - `std` (default): use the [`std::error`] module.
+//! - `std` (default): use the [`std::error`] module. Implies `alloc`.
+//! - `alloc`: use the [`core::error`] module on `no_std` targets with an allocator, where sources
+//!   and fields can use `alloc` types like `Box<dyn core::error::Error>` and `String`. Required by
+//!   `#[from(boxed)]`, which expects `extern crate alloc` without `std`.
+//! - `backtrace`: capture a `std::backtrace::Backtrace` in fields marked with `#[backtrace]` when
+//!   converting with `#[from]`. Implies `std`.
+//! - `provide`: implement `Error::provide` for fields marked with `#[provide]` or `#[backtrace]`.
+//!   This API is unstable, so it requires nightly with `#![feature(error_generic_member_access)]`.
 //!
-//! To use `onlyerror` in a `no_std` environment, disable default features in your Cargo manifest.
+//! To use `onlyerror` in a `no_std` environment, disable default features in your Cargo manifest,
+//! and enable `alloc` if the target has an allocator.
 //!
-//! As of writing, you must add `#![feature(error_in_core)]` to the top-level `lib.rs` or `main.rs`
-//! file to enable the [`core::error`] module. This feature flag is only available on nightly
-//! compilers.
+//! The [`core::error`] module is stable since Rust 1.81. Older compilers require adding
+//! `#![feature(error_in_core)]` to the top-level `lib.rs` or `main.rs` file, which is only
+//! available on nightly. With `alloc`, boxed sources like `Box<dyn core::error::Error + 'static>`
+//! work the same as they do with `std`.
 //!
 //! [`Error`]: derive@Error
 //! [`myn` benchmarks]: https://github.com/parasyte/myn/blob/main/benchmarks.md
@@ -80,15 +247,18 @@
 #![deny(clippy::pedantic)]
 #![allow(clippy::let_underscore_untyped)]
 
-use crate::parser::{Error, ErrorSource, VariantType};
+use crate::parser::Error;
 use myn::utils::spanned_error;
 use proc_macro::{Span, TokenStream};
-use std::{fmt::Write as _, rc::Rc, str::FromStr as _};
+use std::str::FromStr as _;
 
+mod codegen;
 mod parser;
 
-#[allow(clippy::too_many_lines)]
-#[proc_macro_derive(Error, attributes(error, from, source, no_display))]
+#[proc_macro_derive(
+    Error,
+    attributes(error, from, source, no_display, no_error_impl, backtrace, provide)
+)]
 pub fn derive_error(input: TokenStream) -> TokenStream {
     let ast = match Error::parse(input) {
         Ok(ast) => ast,
@@ -100,142 +270,14 @@ pub fn derive_error(input: TokenStream) -> TokenStream {
     #[cfg(not(feature = "std"))]
     let std_crate = "core";
 
-    let name = &ast.name;
-    let error_matches = ast
-        .variants
-        .iter()
-        .filter_map(|v| match &v.source {
-            ErrorSource::From(index) | ErrorSource::Source(index) => {
-                let name = &v.name;
-
-                Some(match &v.ty {
-                    VariantType::Unit => format!("Self::{name} => None,"),
-                    VariantType::Tuple => {
-                        let index_num: usize = index.parse().unwrap_or_default();
-                        let fields = (0..v.fields.len())
-                            .map(|i| if i == index_num { "field," } else { "_," })
-                            .collect::<String>();
-
-                        format!("Self::{name}({fields}) => Some(field),")
-                    }
-                    VariantType::Struct => {
-                        format!("Self::{name} {{ {index}, ..}} => Some({index}),")
-                    }
-                })
-            }
-            ErrorSource::None => None,
-        })
-        .collect::<String>();
-
-    let display_impl = if ast.no_display {
-        String::new()
-    } else {
-        let display = ast.variants.iter().map(|v| {
-            let name = &v.name;
-            let display = &v.display;
-
-            if display.is_empty() {
-                return Err(name);
-            }
-
-            let display_fields =
-                v.display_fields
-                    .iter()
-                    .fold(String::new(), |mut fields, field| {
-                        let _ = write!(fields, "{field},");
-                        fields
-                    });
-
-            Ok(match &v.ty {
-                VariantType::Unit => format!("Self::{name} => write!(f, {display:?}),"),
-                VariantType::Tuple => {
-                    let fields = (0..v.fields.len()).fold(String::new(), |mut fields, i| {
-                        if v.display_fields.contains(&Rc::from(format!("field_{i}"))) {
-                            let _ = write!(fields, "field_{i},");
-                        } else {
-                            let _ = fields.write_str("_,");
-                        }
-                        fields
-                    });
-                    format!("Self::{name}({fields}) => write!(f, {display:?}, {display_fields}),")
-                }
-                VariantType::Struct => {
-                    format!(
-                        "Self::{name} {{ {display_fields} .. }} => \
-                        write!(f, {display:?}, {display_fields}),"
-                    )
-                }
-            })
-        });
-        let mut display_matches = String::new();
-        for res in display {
-            match res {
-                Err(name) => {
-                    return spanned_error("Required error message is missing", name.span());
-                }
-                Ok(msg) => display_matches.push_str(&msg),
-            }
-        }
-        let display_matches = if display_matches.is_empty() {
-            String::from("Ok(())")
-        } else {
-            format!("match self {{ {display_matches} }}")
-        };
-
-        format!(
-            r"impl ::{std_crate}::fmt::Display for {name} {{
-                fn fmt(&self, f: &mut ::{std_crate}::fmt::Formatter<'_>) ->
-                    ::{std_crate}::result::Result<(), ::{std_crate}::fmt::Error>
-                {{
-                    {display_matches}
-                }}
-            }}"
-        )
-    };
-
-    let from_impls = ast
-        .variants
-        .into_iter()
-        .filter_map(|v| match v.source {
-            ErrorSource::From(index) => {
-                let variant_name = v.name;
-                let from_ty = &v.fields[&index];
-                let body = if v.ty == VariantType::Tuple {
-                    format!(r"Self::{variant_name}(value)")
-                } else {
-                    format!(r"Self::{variant_name} {{ {index}: value }}")
-                };
-
-                Some(format!(
-                    r"impl ::{std_crate}::convert::From<{from_ty}> for {name} {{
-                        fn from(value: {from_ty}) -> Self {{
-                            {body}
-                        }}
-                    }}"
-                ))
-            }
-            _ => None,
-        })
-        .collect::<String>();
-
-    let code = TokenStream::from_str(&format!(
-        r"
-            impl ::{std_crate}::error::Error for {name} {{
-                fn source(&self) -> Option<&(dyn ::{std_crate}::error::Error + 'static)> {{
-                    match self {{
-                        {error_matches}
-                        _ => None,
-                    }}
-                }}
-            }}
-
-            {display_impl}
-            {from_impls}
-        "
-    ));
+    // `Box` is only used with the `alloc` feature, which is implied by `std`.
+    #[cfg(feature = "std")]
+    let alloc_crate = "std";
+    #[cfg(not(feature = "std"))]
+    let alloc_crate = "alloc";
 
-    match code {
-        Ok(stream) => stream,
+    match TokenStream::from_str(&codegen::generate(&ast, std_crate, alloc_crate)) {
+        Ok(stream) => codegen::respan_fields(stream, &ast),
         Err(err) => spanned_error(err.to_string(), Span::call_site()),
     }
 }
@@ -1,23 +1,97 @@
 use myn::prelude::*;
-use proc_macro::{Delimiter, Ident, TokenStream, TokenTree};
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
 use std::collections::HashMap;
+use std::ops::Range;
 use std::rc::Rc;
 
 #[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub(crate) struct Error {
-    pub(crate) name: Ident,
+    pub(crate) name: String,
+    /// The visibility of the type, which is copied to generated types.
+    pub(crate) vis: String,
+    pub(crate) generics: Generics,
     pub(crate) variants: Vec<Variant>,
     pub(crate) no_display: bool,
+    /// Skip the `Error` impl, which is written by hand, as with `#[no_error_impl]`.
+    pub(crate) no_error_impl: bool,
+    pub(crate) delegate: Option<String>,
+    /// Text written before the message of every variant.
+    pub(crate) prefix: Option<String>,
+    /// Generate `is_<variant>()` methods.
+    pub(crate) predicates: bool,
+    /// Generate `const fn from_<variant>()` constructors.
+    pub(crate) const_new: bool,
+    /// Display the source of variants without a message.
+    pub(crate) chain_source: bool,
+    /// Generate a `PartialEq` impl that ignores sources.
+    pub(crate) eq: bool,
+    /// Generate a `chain()` method iterating over the error and its sources.
+    pub(crate) chain: bool,
+    /// Append the source to the message of variants with a source.
+    pub(crate) append_source: bool,
+    /// Generate a `Debug` impl that writes the same text as `Display`.
+    pub(crate) debug_as_display: bool,
+    /// Generate a `<Name>Kind` enum and a `kind()` method.
+    pub(crate) kind: bool,
+    /// The case convention for kind names, as with `#[error(rename_all = "snake_case")]`.
+    pub(crate) rename_all: Option<String>,
+    /// The type is a struct, represented by a single variant named after it.
+    pub(crate) is_struct: bool,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Generics {
+    /// Generic parameters with their bounds, as written in `impl<...>` headers.
+    pub(crate) params: String,
+    /// Generic arguments for the type, as in `Error<'a, T, N>`.
+    pub(crate) args: String,
+    pub(crate) where_clause: String,
+    /// Names of the type parameters, which some impls add bounds for.
+    pub(crate) types: Vec<String>,
 }
 
 #[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub(crate) struct Variant {
-    pub(crate) name: Ident,
+    pub(crate) name: String,
     pub(crate) ty: VariantType,
     pub(crate) fields: HashMap<Rc<str>, String>,
-    pub(crate) display: String,
+    /// Where each field type is written, for diagnostics in generated code.
+    pub(crate) field_spans: HashMap<Rc<str>, Span>,
+    pub(crate) display: Option<String>,
     pub(crate) display_fields: Vec<Rc<str>>,
+    pub(crate) display_args: String,
+    /// The message is a `concat!` or `include_str!` call, which is passed to `format_args!`.
+    pub(crate) display_macro: bool,
     pub(crate) source: ErrorSource,
+    /// The `#[from]` attribute, for diagnostics.
+    pub(crate) from_span: Option<Span>,
+    /// Dereference the source field twice, as with `#[source(deref)]`.
+    pub(crate) deref_source: bool,
+    /// The source field is an `Option`, which is returned by `source()` when it is `Some`.
+    pub(crate) optional_source: bool,
+    /// The type inside a `#[from(boxed)]` field, which is also converted by boxing it.
+    pub(crate) boxed_from: Option<String>,
+    /// The `#[from(with = ...)]` closure that builds the variant from its source.
+    pub(crate) from_with: Option<String>,
+    /// Forward `Display` and `source()` to the only field, as with `#[error(transparent)]`.
+    pub(crate) transparent: bool,
+    /// Forward `source()` to the only field, for `#[from]` fields displayed with `#[error("{0}")]`.
+    pub(crate) forward_source: bool,
+    /// `Display` calls a hand-written `fmt_<variant>()` method, as with `#[error(skip_display)]`.
+    pub(crate) skip_display: bool,
+    /// `Display` calls the function from `#[error(fmt = path)]` with the fields and formatter.
+    pub(crate) fmt: Option<String>,
+    /// Field names in declaration order.
+    pub(crate) field_order: Vec<Rc<str>>,
+    /// The field marked with `#[backtrace]`, which is captured by `From`.
+    pub(crate) backtrace: Option<Rc<str>>,
+    /// Fields marked with `#[provide]`.
+    pub(crate) provide: Vec<Rc<str>>,
+    /// The error code from `#[error(code = N)]`.
+    pub(crate) code: Option<u32>,
+    pub(crate) deprecated: bool,
 }
 
 #[derive(Debug, PartialEq)]
@@ -27,10 +101,22 @@ pub(crate) enum VariantType {
     Struct,
 }
 
+/// How doc comments are used as a variant message.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Docs {
+    /// Lines are reflowed like a rustdoc summary.
+    Summary,
+    /// Line breaks are kept, from `#[error(multiline_docs)]`.
+    Multiline,
+    /// Doc comments only document the variant, because a constant message applies.
+    Ignored,
+}
+
 #[derive(Debug)]
 pub(crate) struct Field {
     attrs: Vec<Attribute>,
     path: String,
+    span: Span,
 }
 
 #[derive(Debug)]
@@ -47,57 +133,666 @@ pub(crate) struct OrderedMap<T> {
 }
 
 impl Error {
+    #[allow(clippy::too_many_lines)]
     pub(crate) fn parse(input: TokenStream) -> Result<Self, TokenStream> {
         let mut input = input.into_token_iter();
         let attributes = input.parse_attributes()?;
-        input.parse_visibility()?;
-        input.expect_ident("enum")?;
+        let vis = parse_type_visibility(&mut input);
+        let is_struct = input
+            .next_if(
+                |tree| matches!(tree, TokenTree::Ident(ident) if ident.to_string() == "struct"),
+            )
+            .is_some();
+        if !is_struct {
+            input.expect_ident("enum")?;
+        }
         let name = input.try_ident()?;
+        let mut generics = Generics::parse(&mut input)?;
+
+        let no_display = attributes
+            .iter()
+            .any(|attr| attr.name.to_string() == "no_display");
+        let no_error_impl = attributes
+            .iter()
+            .any(|attr| attr.name.to_string() == "no_error_impl");
+        let mut delegate = None;
+        let mut prefix = None;
+        let mut predicates = false;
+        let mut const_new = false;
+        let mut chain_source = false;
+        let mut eq = false;
+        let mut chain = false;
+        let mut append_source = false;
+        let mut debug_as_display = false;
+        let mut kind = false;
+        let mut rename_all = None;
+        let mut multiline_docs = false;
+
+        // The `#[error(...)]` attributes on a struct are its message, not options.
+        let (template_attrs, option_attrs): (Vec<_>, Vec<_>) = attributes
+            .iter()
+            .filter(|attr| !is_struct && attr.name.to_string() == "error")
+            .partition(|attr| is_message_attr(attr));
+        let template = match template_attrs.as_slice() {
+            [] => None,
+            [attr] => Some(parse_template(attr)?),
+            [_, attr, ..] => {
+                return Err(spanned_error(
+                    "Only one default message is allowed",
+                    attr.name.span(),
+                ));
+            }
+        };
+        for (option, value) in parse_options(option_attrs)? {
+            match (option.to_string().as_str(), value) {
+                ("delegate", Some(value)) if !no_display => delegate = Some(value.to_string()),
+                ("delegate", Some(_)) => {
+                    return Err(spanned_error(
+                        "#[error(delegate = ...)] cannot be used with #[no_display]",
+                        option.span(),
+                    ));
+                }
+                ("multiline_docs", None) => multiline_docs = true,
+                ("predicates", None) => predicates = true,
+                ("const_new", None) => const_new = true,
+                ("chain_source", None) => chain_source = true,
+                ("eq", None) => eq = true,
+                ("chain", None) => chain = true,
+                ("append_source", None) => append_source = true,
+                ("kind", None) => kind = true,
+                ("debug_as_display", None) => {
+                    // Other derives in the same attribute are not visible to the macro.
+                    if attributes.iter().any(is_debug_derive) {
+                        return Err(spanned_error(
+                            "#[error(debug_as_display)] cannot be used with #[derive(Debug)]",
+                            option.span(),
+                        ));
+                    }
+
+                    debug_as_display = true;
+                }
+                ("prefix", Some(value)) => {
+                    prefix = Some(parse_string(&value.into_token_iter().try_lit()?)?);
+                }
+                ("rename_all", Some(value)) => {
+                    let lit = value.into_token_iter().try_lit()?;
+                    let rule = parse_string(&lit)?;
+                    if !RENAME_RULES.contains(&rule.as_str()) {
+                        let msg = format!(
+                            "Unknown case convention `{rule}`. Expected one of {}",
+                            RENAME_RULES.map(|rule| format!("`{rule}`")).join(", "),
+                        );
+
+                        return Err(spanned_error(msg, lit.span()));
+                    }
+
+                    rename_all = Some((rule, option.span()));
+                }
+                _ => {
+                    return Err(spanned_error(
+                        format!("Unknown option `{option}`"),
+                        option.span(),
+                    ))
+                }
+            }
+        }
+
+        // Only the kind names are renamed, since methods are always `snake_case`.
+        let rename_all = match rename_all {
+            Some((_, span)) if !kind => {
+                return Err(spanned_error(
+                    "#[error(rename_all = ...)] requires #[error(kind)]",
+                    span,
+                ));
+            }
+            rename_all => rename_all.map(|(rule, _)| rule),
+        };
+
+        if is_struct {
+            let group = parse_body(&mut input);
+            if generics.where_clause.is_empty() {
+                generics.where_clause = parse_where_clause(&mut input);
+            }
+            if !matches!(&group, Some(group) if group.delimiter() == Delimiter::Brace) {
+                input.expect_punct(';')?;
+            }
+
+            let docs = if multiline_docs {
+                Docs::Multiline
+            } else {
+                Docs::Summary
+            };
+            let variant = Variant::from_parts(&attributes, &name, group, docs)?;
+            if variant.display.is_none()
+                && !variant.transparent
+                && !variant.skip_display
+                && variant.fmt.is_none()
+                && !no_display
+            {
+                return Err(spanned_error(
+                    "Required error message is missing",
+                    name.span(),
+                ));
+            }
+            if let Some(tree) = input.next() {
+                return Err(spanned_error("Unexpected token", tree.span()));
+            }
+
+            return Ok(Self {
+                name: name.to_string(),
+                vis,
+                generics,
+                variants: vec![variant],
+                no_display,
+                no_error_impl,
+                delegate,
+                prefix,
+                predicates,
+                const_new,
+                chain_source,
+                eq,
+                chain,
+                append_source,
+                debug_as_display,
+                kind,
+                rename_all,
+                is_struct,
+            });
+        }
+
+        if template.is_some() && (no_display || delegate.is_some() || chain_source) {
+            return Err(spanned_error(
+                "A default message cannot be used with #[no_display], #[error(delegate = ...)], \
+                or #[error(chain_source)]",
+                name.span(),
+            ));
+        }
 
         let mut content = input.expect_group(Delimiter::Brace)?;
         let mut variants = vec![];
+        let mut from_types = HashMap::new();
+        let mut method_names = HashMap::new();
+        let mut kind_names = HashMap::new();
+        let mut codes = HashMap::new();
+        let mut missing_code = None;
+
+        // A default message without `{variant}` is a constant message for the whole enum.
+        let constant = template
+            .as_ref()
+            .filter(|template| format_references(template).is_empty());
+
+        let docs = match (constant, multiline_docs) {
+            (Some(_), _) => Docs::Ignored,
+            (None, true) => Docs::Multiline,
+            (None, false) => Docs::Summary,
+        };
 
         while content.peek().is_some() {
-            variants.push(Variant::parse(&mut content)?);
-        }
+            if constant.is_some() {
+                let attrs = content.clone().parse_attributes()?;
+                if let Some(attr) = attrs
+                    .iter()
+                    .find(|attr| attr.name.to_string() == "error" && is_message_attr(attr))
+                {
+                    return Err(spanned_error(
+                        "Variant messages cannot be combined with a constant message on the \
+                        enum. Use `{variant}` in the enum message to make it a default instead",
+                        attr.name.span(),
+                    ));
+                }
+            }
+
+            let (mut variant, span) = Variant::parse(&mut content, docs)?;
 
-        match input.next() {
-            None => Ok(Self {
-                name,
-                variants,
-                no_display: attributes
+            if let Some(constant) = constant {
+                if variant.transparent || variant.skip_display || variant.fmt.is_some() {
+                    let msg = format!(
+                        "Variant `{}` cannot be displayed on its own with a constant message on \
+                        the enum",
+                        variant.name,
+                    );
+
+                    return Err(spanned_error(msg, span));
+                }
+
+                variant.display = Some(constant.clone());
+            }
+
+            // Variants without a message use the default message with their own name.
+            if let (None, false, false, None, Some(template)) = (
+                &variant.display,
+                variant.transparent,
+                variant.skip_display,
+                &variant.fmt,
+                &template,
+            ) {
+                variant.display = Some(template.clone());
+                variant.display_args = format!("variant = {:?}", variant.name);
+            }
+
+            if variant.display.is_none()
+                && !variant.transparent
+                && !variant.skip_display
+                && variant.fmt.is_none()
+                && (!chain_source || variant.source.as_ref().is_none() || variant.optional_source)
+                && !no_display
+                && delegate.is_none()
+            {
+                return Err(spanned_error("Required error message is missing", span));
+            }
+            if variant.skip_display && (no_display || delegate.is_some()) {
+                return Err(spanned_error(
+                    "#[error(skip_display)] cannot be used when `Display` is not derived",
+                    span,
+                ));
+            }
+            if variant.fmt.is_some() && (no_display || delegate.is_some()) {
+                return Err(spanned_error(
+                    "#[error(fmt = ...)] cannot be used when `Display` is not derived",
+                    span,
+                ));
+            }
+
+            // Each type can only be converted into a single variant.
+            if let ErrorSource::From(index) = &variant.source {
+                let from_ty = variant.fields[index].clone();
+                for from_ty in [Some(from_ty), variant.boxed_from.clone()]
                     .into_iter()
-                    .any(|attr| attr.name.to_string() == "no_display"),
-            }),
-            tree => Err(spanned_error("Unexpected token", tree.as_span())),
+                    .flatten()
+                {
+                    let from_span = variant.from_span.unwrap_or(span);
+                    if let Some((prev, prev_span)) =
+                        from_types.insert(from_ty.clone(), (variant.name.clone(), from_span))
+                    {
+                        let msg = format!(
+                            "#[from] `{from_ty}` on variant `{}` conflicts with variant `{prev}`. \
+                            Only one variant can be converted from each type, so use #[source] \
+                            for the other",
+                            variant.name,
+                        );
+                        let note =
+                            format!("Variant `{prev}` is first converted from `{from_ty}` here");
+
+                        let mut error = spanned_error(msg, from_span);
+                        error.extend(spanned_error(note, prev_span));
+
+                        return Err(error);
+                    }
+                }
+            }
+
+            // Error codes identify a single variant.
+            match variant.code {
+                Some(code) => {
+                    if let Some(prev) = codes.insert(code, variant.name.clone()) {
+                        let msg = format!("Error code {code} is already used by variant `{prev}`");
+
+                        return Err(spanned_error(msg, span));
+                    }
+                }
+                None => {
+                    missing_code.get_or_insert(span);
+                }
+            }
+
+            // Each variant needs distinct generated methods.
+            let snake_name = snake_case(&variant.name);
+            let mut methods = vec![];
+            if predicates {
+                methods.push(format!("is_{snake_name}"));
+            }
+            if variant.skip_display {
+                methods.push(format!("fmt_{snake_name}"));
+            }
+            if const_new
+                && matches!(variant.source, ErrorSource::From(_))
+                && variant.from_with.is_none()
+                && variant.fields.len() == 1
+            {
+                methods.push(format!("from_{snake_name}"));
+            }
+            for method in methods {
+                if let Some(prev) = method_names.insert(method.clone(), variant.name.clone()) {
+                    let msg = format!(
+                        "Generated method `{method}` for variant `{}` conflicts with variant \
+                        `{prev}`",
+                        variant.name,
+                    );
+
+                    return Err(spanned_error(msg, span));
+                }
+            }
+
+            // Renaming can be lossy, as with `IoError` and `IOError` in `snake_case`.
+            if kind {
+                let kind_name =
+                    rename(&variant.name, rename_all.as_deref().unwrap_or("PascalCase"));
+                if let Some(prev) = kind_names.insert(kind_name.clone(), variant.name.clone()) {
+                    let msg = format!(
+                        "Kind name `{kind_name}` for variant `{}` conflicts with variant `{prev}`",
+                        variant.name,
+                    );
+
+                    return Err(spanned_error(msg, span));
+                }
+            }
+
+            variants.push(variant);
+        }
+
+        // Zero-variant enums cannot have a `#[repr(u8)]`.
+        if kind && variants.is_empty() {
+            return Err(spanned_error(
+                "#[error(kind)] requires at least one variant",
+                name.span(),
+            ));
+        }
+
+        if let (false, Some(span)) = (codes.is_empty(), missing_code) {
+            return Err(spanned_error(
+                "Error code is missing. Every variant needs #[error(code = N)] when any has one",
+                span,
+            ));
+        }
+
+        if let Some(tree) = input.next() {
+            return Err(spanned_error("Unexpected token", tree.span()));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            vis,
+            generics,
+            variants,
+            no_display,
+            no_error_impl,
+            delegate,
+            prefix,
+            predicates,
+            const_new,
+            chain_source,
+            eq,
+            chain,
+            append_source,
+            debug_as_display,
+            kind,
+            rename_all,
+            is_struct,
+        })
+    }
+}
+
+impl Generics {
+    fn parse(input: &mut TokenIter) -> Result<Self, TokenStream> {
+        let mut generics = Self::default();
+
+        if input
+            .next_if(|tree| matches!(tree, TokenTree::Punct(punct) if punct.as_char() == '<'))
+            .is_some()
+        {
+            let mut tokens = vec![];
+            let mut depth = 0;
+            let mut arrow = false;
+
+            loop {
+                let tree = input
+                    .next()
+                    .ok_or_else(|| spanned_error("Expected `>`", Span::call_site()))?;
+
+                if let TokenTree::Punct(punct) = &tree {
+                    match punct.as_char() {
+                        '<' => depth += 1,
+                        '>' if !arrow && depth == 0 => break,
+                        '>' if !arrow => depth -= 1,
+                        _ => (),
+                    }
+                    arrow = punct.as_char() == '-';
+                } else {
+                    arrow = false;
+                }
+                tokens.push(tree);
+            }
+
+            let mut params = vec![];
+            let mut args = vec![];
+            let mut types = vec![];
+            for param in split_punct(tokens, ',') {
+                // Defaults are only allowed on the type definition.
+                let param = split_punct(param, '=').swap_remove(0);
+                let arg = match param.as_slice() {
+                    [TokenTree::Punct(punct), TokenTree::Ident(lifetime), ..]
+                        if punct.as_char() == '\'' =>
+                    {
+                        format!("'{lifetime}")
+                    }
+                    [TokenTree::Ident(keyword), TokenTree::Ident(name), ..]
+                        if keyword.to_string() == "const" =>
+                    {
+                        name.to_string()
+                    }
+                    [TokenTree::Ident(name), ..] => {
+                        types.push(name.to_string());
+                        name.to_string()
+                    }
+                    [] => continue,
+                    [tree, ..] => return Err(spanned_error("Unexpected token", tree.span())),
+                };
+
+                params.push(param.into_iter().collect::<TokenStream>().to_string());
+                args.push(arg);
+            }
+
+            generics.params = format!("<{}>", params.join(", "));
+            generics.args = format!("<{}>", args.join(", "));
+            generics.types = types;
+        }
+
+        generics.where_clause = parse_where_clause(input);
+
+        Ok(generics)
+    }
+
+    /// Get the `where` clause with `bound` added for every type parameter.
+    pub(crate) fn where_clause_with(&self, bound: &str) -> String {
+        self.where_clause_and(self.types.iter().map(|ty| format!("{ty}: {bound}")))
+    }
+
+    /// Get the `where` clause with extra `predicates`.
+    pub(crate) fn where_clause_and(&self, predicates: impl IntoIterator<Item = String>) -> String {
+        let bounds = predicates.into_iter().collect::<Vec<_>>().join(", ");
+
+        match (self.where_clause.as_str(), bounds.as_str()) {
+            (where_clause, "") => where_clause.to_string(),
+            ("", bounds) => format!("where {bounds}"),
+            (where_clause, bounds) => {
+                format!(
+                    "{}, {bounds}",
+                    where_clause.trim_end().trim_end_matches(',')
+                )
+            }
+        }
+    }
+}
+
+/// Convert a `CamelCase` variant name to `snake_case`.
+///
+/// Acronyms are kept together, so `IOError` becomes `io_error`.
+pub(crate) fn snake_case(name: &str) -> String {
+    let chars = name.trim_start_matches("r#").chars().collect::<Vec<_>>();
+    let mut output = String::new();
+
+    for (i, ch) in chars.iter().enumerate() {
+        if ch.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if prev != '_' && (!prev.is_uppercase() || next_lower) {
+                output.push('_');
+            }
         }
+        output.extend(ch.to_lowercase());
+    }
+
+    output
+}
+
+/// Case conventions for `#[error(rename_all = "...")]`, named like serde's.
+const RENAME_RULES: [&str; 8] = [
+    "lowercase",
+    "UPPERCASE",
+    "PascalCase",
+    "camelCase",
+    "snake_case",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "SCREAMING-KEBAB-CASE",
+];
+
+/// Convert a variant name to one of the [`RENAME_RULES`] case conventions.
+///
+/// Words are split like [`snake_case`], so acronyms stay together, as in `HTTPError` ->
+/// `http-error`.
+pub(crate) fn rename(name: &str, rule: &str) -> String {
+    let name = name.trim_start_matches("r#");
+    let snake = snake_case(name);
+
+    match rule {
+        "lowercase" => name.to_lowercase(),
+        "UPPERCASE" => name.to_uppercase(),
+        "camelCase" => {
+            let mut chars = name.chars();
+            chars
+                .next()
+                .map(|first| first.to_lowercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+        "snake_case" => snake,
+        "SCREAMING_SNAKE_CASE" => snake.to_uppercase(),
+        "kebab-case" => snake.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => snake.replace('_', "-").to_uppercase(),
+        _ => name.to_string(),
+    }
+}
+
+/// Parse a `where` clause up to the body or the `;` ending a tuple struct.
+fn parse_where_clause(input: &mut TokenIter) -> String {
+    let mut where_clause = String::new();
+    if input
+        .next_if(|tree| matches!(tree, TokenTree::Ident(ident) if ident.to_string() == "where"))
+        .is_some()
+    {
+        let mut tokens = vec![];
+        while let Some(tree) = input.next_if(|tree| match tree {
+            TokenTree::Group(group) => group.delimiter() != Delimiter::Brace,
+            TokenTree::Punct(punct) => punct.as_char() != ';',
+            _ => true,
+        }) {
+            tokens.push(tree);
+        }
+
+        // Collecting the tokens preserves joint punctuation like `::`.
+        where_clause = format!("where {}", tokens.into_iter().collect::<TokenStream>());
+    }
+
+    where_clause
+}
+
+/// Parse the optional field group of a variant or struct.
+fn parse_body(input: &mut TokenIter) -> Option<Group> {
+    match input.next_if(|tree| matches!(tree, TokenTree::Group(_)))? {
+        TokenTree::Group(group) => Some(group),
+        _ => None,
     }
 }
 
 impl Variant {
-    pub(crate) fn parse(input: &mut TokenIter) -> Result<Self, TokenStream> {
+    pub(crate) fn parse(input: &mut TokenIter, docs: Docs) -> Result<(Self, Span), TokenStream> {
         let attrs = input.parse_attributes()?;
         let name = input.try_ident()?;
+        let group = parse_body(input);
 
+        if group.is_some() {
+            let _ = input.expect_punct(',');
+        } else {
+            // Skip everything before ',', which the last variant may not have.
+            while input.peek().is_some() && input.expect_punct(',').is_err() {}
+        }
+
+        let variant = Self::from_parts(&attrs, &name, group, docs)?;
+
+        Ok((variant, name.span()))
+    }
+
+    /// Build a variant from its attributes, name, and optional field group.
+    ///
+    /// Structs are parsed with this as a single variant named after the struct.
+    #[allow(clippy::too_many_lines)]
+    pub(crate) fn from_parts(
+        attrs: &[Attribute],
+        name: &Ident,
+        group: Option<Group>,
+        docs: Docs,
+    ) -> Result<Self, TokenStream> {
         let mut fields = HashMap::new();
+        let mut field_spans = HashMap::new();
+        let mut field_order = vec![];
         let mut source = ErrorSource::None;
-        let group = if let Some(TokenTree::Group(group)) = input.peek() {
-            let group = group.clone();
-            input.next();
-            Some(group)
-        } else {
-            None
-        };
+        let mut from_span = None;
+        let mut deref_source = false;
+        let mut boxed_from = None;
+        let mut from_with = None;
+        let mut optional_source = false;
+        let mut backtrace = None;
+        let mut provide = vec![];
         let ty = if let Some(group) = group {
             let (ty, map) = match group.delimiter() {
-                Delimiter::Parenthesis => (VariantType::Tuple, parse_tuple_fields(group.stream())?),
-                Delimiter::Brace => (VariantType::Struct, parse_struct_fields(group.stream())?),
+                Delimiter::Parenthesis => (
+                    VariantType::Tuple,
+                    parse_tuple_fields(group.stream(), name)?,
+                ),
+                Delimiter::Brace => (
+                    VariantType::Struct,
+                    parse_struct_fields(group.stream(), name)?,
+                ),
                 _ => return Err(spanned_error("Unexpected delimiter", group.span())),
             };
 
             // Resolve error source.
-            let num_fields = map.len();
             for (key, field) in map.into_iter() {
+                let attr = field
+                    .attrs
+                    .iter()
+                    .find(|attr| attr.name.to_string() == "backtrace");
+                if let Some(attr) = attr {
+                    if !cfg!(feature = "backtrace") {
+                        return Err(spanned_error(
+                            "#[backtrace] requires the `backtrace` feature",
+                            attr.name.span(),
+                        ));
+                    }
+                    if let Some(name) = &backtrace {
+                        let msg = format!(
+                            "#[backtrace] can only be used once. Previously seen on field `{name}`"
+                        );
+
+                        return Err(spanned_error(msg, attr.name.span()));
+                    }
+                    backtrace = Some(key.clone());
+                }
+
+                let attr = field
+                    .attrs
+                    .iter()
+                    .find(|attr| attr.name.to_string() == "provide");
+                if let Some(attr) = attr {
+                    if !cfg!(feature = "provide") {
+                        return Err(spanned_error(
+                            "#[provide] requires the `provide` feature",
+                            attr.name.span(),
+                        ));
+                    }
+                    provide.push(key.clone());
+                }
+
                 let attrs = field
                     .attrs
                     .iter()
@@ -114,95 +809,1065 @@ impl Variant {
                         return Err(spanned_error(msg, attr.name.span()));
                     }
 
-                    if attr.name.to_string() == "from" {
-                        if num_fields > 1 {
+                    // Tuples never implement `Error`, so they cannot be a source.
+                    if field.path.starts_with('(') {
+                        let msg = format!(
+                            "#[{}] cannot be used with tuple type `{}`. \
+                            Put each element in its own field instead",
+                            attr.name, field.path,
+                        );
+
+                        return Err(spanned_error(msg, attr.name.span()));
+                    }
+
+                    // `source()` returns `dyn Error + 'static`, which cannot borrow from the enum.
+                    if field.path.starts_with('&') && !field.path.starts_with("&'static ") {
+                        let msg = format!(
+                            "#[{}] cannot be used with borrowed type `{}`. \
+                            Sources must be owned or `'static`",
+                            attr.name, field.path,
+                        );
+
+                        return Err(spanned_error(msg, attr.name.span()));
+                    }
+
+                    let is_from = attr.name.to_string() == "from";
+                    optional_source = option_type(&field.path).is_some();
+                    let mut default_from = false;
+                    let mut boxed = None;
+                    let mut with = None;
+                    for (option, value) in parse_options([attr])? {
+                        match (option.to_string().as_str(), value) {
+                            ("deref", None) => deref_source = true,
+                            ("from", None) if !is_from => default_from = true,
+                            ("boxed", None) => boxed = Some(option),
+                            ("with", Some(value)) => with = Some((option, value)),
+                            ("any", None) if is_from || default_from => {
+                                return Err(catch_all_error("#[from(any)]", option.span()));
+                            }
+                            _ => {
+                                return Err(spanned_error(
+                                    format!("Unknown option `{option}`"),
+                                    option.span(),
+                                ))
+                            }
+                        }
+                    }
+
+                    // Boxing the inner type allocates.
+                    if let Some(option) = boxed {
+                        if !is_from && !default_from {
+                            return Err(spanned_error(
+                                "#[source(boxed)] requires #[source(from)]",
+                                option.span(),
+                            ));
+                        }
+                        if !cfg!(feature = "alloc") {
+                            return Err(spanned_error(
+                                "#[from(boxed)] requires the `alloc` feature",
+                                option.span(),
+                            ));
+                        }
+                        let Some(inner) =
+                            boxed_type(&field.path).filter(|ty| !ty.starts_with("dyn "))
+                        else {
+                            return Err(spanned_error(
+                                "#[from(boxed)] can only be used with `Box<T>` fields of a sized \
+                                type `T`",
+                                option.span(),
+                            ));
+                        };
+
+                        boxed_from = Some(inner.to_string());
+                    }
+
+                    // The closure replaces the defaults for the other fields.
+                    if let Some((option, value)) = with {
+                        if !is_from && !default_from {
                             return Err(spanned_error(
-                                "#[from] can only be used with a single field",
-                                name.span(),
+                                "#[source(with = ...)] requires #[source(from)]",
+                                option.span(),
                             ));
                         }
+                        check_from_with(name, &value, option.span())?;
+
+                        from_with = Some(value.to_string());
+                    }
+
+                    // An absent source cannot be converted from.
+                    if optional_source && (is_from || default_from) {
+                        let msg = format!(
+                            "#[from] cannot be used with optional type `{}`. \
+                            Use #[source] instead",
+                            field.path,
+                        );
 
+                        return Err(spanned_error(msg, attr.name.span()));
+                    }
+
+                    // The other fields are filled with defaults.
+                    if is_from || default_from {
                         source = ErrorSource::From(key.clone());
+                        from_span = Some(attr.name.span());
                     } else {
                         source = ErrorSource::Source(key.clone());
                     }
                 }
 
+                field_order.push(key.clone());
+                field_spans.insert(key.clone(), field.span);
                 fields.insert(key, field.path);
             }
 
-            let _ = input.expect_punct(',');
-
             ty
         } else {
-            // Skip everything before ','
-            while input.expect_punct(',').is_err() {}
             VariantType::Unit
         };
 
+        // Options like `#[error(code = 42)]` are written in their own attribute, since they would
+        // be named arguments after a message.
+        let (option_attrs, message_attrs): (Vec<_>, Vec<_>) = attrs
+            .iter()
+            .filter(|attr| attr.name.to_string() == "error")
+            .partition(|attr| is_option_attr(attr));
+        let mut code = None;
+        let mut fmt = None;
+        for (option, value) in parse_options(option_attrs)? {
+            match (option.to_string().as_str(), value) {
+                ("code", Some(value)) => code = Some(parse_code(value)?),
+                ("fmt", Some(value)) => {
+                    // A formatting function replaces the message.
+                    if let Some(attr) = message_attrs.first() {
+                        return Err(spanned_error(
+                            "#[error(fmt = ...)] cannot be combined with a message",
+                            attr.name.span(),
+                        ));
+                    }
+
+                    fmt = Some(value.to_string());
+                }
+                _ => {
+                    return Err(spanned_error(
+                        format!("Unknown option `{option}`"),
+                        option.span(),
+                    ))
+                }
+            }
+        }
+
         // #[error] attributes override doc comments
-        let mut display = if let Some(mut tree) = attrs
+        let mut display_args = vec![];
+        let mut display_span = name.span();
+        let mut transparent = false;
+        let mut skip_display = false;
+        let mut display_macro = false;
+        let mut display = if let Some(mut tree) = message_attrs
             .iter()
-            .find_map(|attr| (attr.name.to_string() == "error").then_some(attr.tree.clone()))
+            .map(|attr| attr.tree.clone())
+            .next()
             .and_then(|mut tree| tree.expect_group(Delimiter::Parenthesis).ok())
         {
-            tree.try_lit()?.as_string()?
+            // Attribute arguments are not expanded, so a macro call can never produce the message.
+            let mut ahead = tree.clone();
+            if let Ok(ident) = ahead.try_ident() {
+                let rest = ahead.collect::<Vec<_>>();
+                if matches!(rest.first(), Some(TokenTree::Punct(punct)) if punct.as_char() == '!') {
+                    // `format_args!` expands these itself, so they can be passed through.
+                    if ["concat", "include_str"].contains(&ident.to_string().as_str()) {
+                        display_macro = true;
+                    } else {
+                        let msg = format!(
+                            "Macros like `{ident}!` are not expanded in #[error(...)] \
+                            attributes. Use a string literal, `concat!`, or `include_str!` instead"
+                        );
+
+                        return Err(spanned_error(msg, ident.span()));
+                    }
+                }
+
+                if ident.to_string() == "catch_all" {
+                    return Err(catch_all_error("#[error(catch_all)]", ident.span()));
+                }
+
+                // Display and source are forwarded to the only field.
+                if ident.to_string() == "transparent" {
+                    if let Some(tree) = rest.first() {
+                        return Err(spanned_error(
+                            "#[error(transparent)] cannot be combined with a message",
+                            tree.span(),
+                        ));
+                    }
+                    if fields.len() != 1 {
+                        return Err(spanned_error(
+                            "#[error(transparent)] can only be used with a single field",
+                            ident.span(),
+                        ));
+                    }
+
+                    transparent = true;
+                }
+
+                // Display calls a hand-written method instead.
+                if ident.to_string() == "skip_display" {
+                    if let Some(tree) = rest.first() {
+                        return Err(spanned_error(
+                            "#[error(skip_display)] cannot be combined with a message",
+                            tree.span(),
+                        ));
+                    }
+
+                    skip_display = true;
+                }
+            }
+
+            if transparent || skip_display {
+                None
+            } else if display_macro {
+                display_span = tree.peek().map_or(display_span, TokenTree::span);
+                let call = tree.by_ref().take(3).collect::<TokenStream>();
+
+                // Explicit format arguments are passed through verbatim.
+                if tree.peek().is_some() {
+                    tree.expect_punct(',')?;
+                    display_args = parse_format_args(tree);
+                }
+
+                Some(call.to_string())
+            } else {
+                let lit = tree.try_lit()?;
+                display_span = lit.span();
+                let mut display = parse_string(&lit)?;
+
+                // Adjacent string literals are concatenated.
+                while matches!(tree.peek(), Some(TokenTree::Literal(_))) {
+                    display.push_str(&parse_string(&tree.try_lit()?)?);
+                }
+
+                // Explicit format arguments are passed through verbatim.
+                if tree.peek().is_some() {
+                    tree.expect_punct(',')?;
+                    display_args = parse_format_args(tree);
+                }
+
+                Some(display)
+            }
+        } else if docs == Docs::Ignored {
+            None
         } else {
-            get_doc_comment(&attrs).join("")
+            let doc = doc_comment(attrs);
+            let doc = if docs == Docs::Multiline {
+                doc.iter()
+                    .map(|line| line.trim())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            } else {
+                // Lines are reflowed like a rustdoc summary.
+                doc.iter()
+                    .flat_map(|line| line.split_whitespace())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+
+            Some(doc).filter(|doc| !doc.trim().is_empty() && fmt.is_none())
+        }
+        .map(|display| display.trim().to_string());
+
+        // The message only displays the `#[from]` field, so the field is not reported twice in an
+        // error chain.
+        let forward_source = ty == VariantType::Tuple
+            && fields.len() == 1
+            && matches!(source, ErrorSource::From(_))
+            && display.as_deref() == Some("{0}")
+            && display_args.is_empty();
+
+        // Named arguments and tuple field aliases share one namespace.
+        let mut arg_names = vec![];
+        for arg in &display_args {
+            let Some(name) = parse_named_arg(arg) else {
+                continue;
+            };
+            if arg_names.contains(&name) {
+                let msg = format!(
+                    "Duplicate argument `{name}` in message. Each named argument can only be \
+                    given once"
+                );
+                let span = arg
+                    .clone()
+                    .into_iter()
+                    .next()
+                    .map_or(display_span, |tree| tree.span());
+
+                return Err(spanned_error(msg, span));
+            }
+            arg_names.push(name);
+        }
+
+        // Tuple fields can be named with `name = index` arguments.
+        let mut aliases = HashMap::new();
+        if ty == VariantType::Tuple {
+            let mut args = vec![];
+            for arg in display_args {
+                let Some((alias, index)) = parse_alias(&arg) else {
+                    args.push(arg);
+                    continue;
+                };
+                if !fields.contains_key(index.as_str()) {
+                    let msg = format!(
+                        "Field index {index} for `{alias}` is out of range. Expected an index \
+                        less than {}",
+                        fields.len(),
+                    );
+                    let span = arg
+                        .into_iter()
+                        .last()
+                        .map_or(display_span, |tree| tree.span());
+
+                    return Err(spanned_error(msg, span));
+                }
+                aliases.insert(alias, index);
+            }
+            display_args = args;
+
+            // The source field can always be referenced as `{source}`.
+            if let Some(index) = source.as_ref() {
+                aliases
+                    .entry("source".to_string())
+                    .or_insert_with(|| index.to_string());
+            }
         }
-        .trim()
-        .to_string();
 
         // Collect field references.
-        let display_fields: Vec<Rc<str>> = display
-            .split('{')
-            .skip(1)
-            .filter_map(|s| s.split('}').next())
-            .filter_map(|s| s.split(':').next())
-            .map(|name| {
-                if ty == VariantType::Tuple {
-                    Rc::from(format!("field_{name}"))
+        let explicit_args = display_args
+            .iter()
+            .filter_map(parse_named_arg)
+            .collect::<Vec<_>>();
+        let mut display_fields: Vec<Rc<str>> = vec![];
+        let mut renames = vec![];
+        let mut named_fields: Vec<(String, Rc<str>, bool)> = vec![];
+        let has_positional_args = display_args.len() > explicit_args.len();
+        // The text of a macro message is unknown, so it cannot reference fields.
+        let text = display
+            .as_deref()
+            .filter(|_| !display_macro)
+            .unwrap_or_default();
+        for range in format_references(text) {
+            let name = &text[range.clone()];
+            if !is_reference(name) {
+                let msg = format!(
+                    "Invalid placeholder `{{{name}}}`. \
+                    Expected a field index like `{{0}}` or a name like `{{field}}`"
+                );
+
+                return Err(spanned_error(msg, display_span));
+            }
+
+            // Explicit arguments are not fields.
+            let is_index = name.bytes().all(|ch| ch.is_ascii_digit());
+            if explicit_args.iter().any(|arg| arg == name)
+                || (is_index && ty != VariantType::Tuple && has_positional_args)
+            {
+                continue;
+            }
+
+            let field: Rc<str> = if let Some(index) = aliases.get(name) {
+                Rc::from(format!("field_{index}"))
+            } else if ty == VariantType::Tuple {
+                Rc::from(format!("field_{name}"))
+            } else if is_index {
+                // Struct fields can be referenced by their declaration order.
+                match name.parse::<usize>().ok().and_then(|i| field_order.get(i)) {
+                    Some(key) => key.clone(),
+                    None => Rc::from(name),
+                }
+            } else if fields.contains_key(format!("r#{name}").as_str()) {
+                Rc::from(format!("r#{name}"))
+            } else {
+                Rc::from(name)
+            };
+
+            // Unknown fields would only be reported as missing values in the generated code.
+            let key = if ty == VariantType::Tuple {
+                field.strip_prefix("field_")
+            } else {
+                Some(&*field)
+            };
+            if !key.is_some_and(|key| fields.contains_key(key)) {
+                let mut valid = field_order
+                    .iter()
+                    .map(|key| format!("`{}`", key.trim_start_matches("r#")))
+                    .collect::<Vec<_>>();
+                let mut names = aliases.keys().collect::<Vec<_>>();
+                names.sort();
+                valid.extend(names.into_iter().map(|alias| format!("`{alias}`")));
+                let msg = if valid.is_empty() {
+                    format!("Unknown field `{name}` in message. The variant has no fields")
                 } else {
-                    Rc::from(name)
-                }
-            })
-            .collect();
-
-        // Remove field references from format string.
-        for field in &display_fields {
-            // Special case for tuples
-            if ty == VariantType::Tuple {
-                if let Some(num) = field.strip_prefix("field_") {
-                    display = display
-                        .replace(&format!("{{{num}:"), "{:")
-                        .replace(&format!("{{{num}}}"), "{}");
-                    continue;
+                    format!(
+                        "Unknown field `{name}` in message. Expected one of {}",
+                        valid.join(", "),
+                    )
+                };
+
+                return Err(spanned_error(msg, display_span));
+            }
+
+            // The name of the argument in the format string, after renaming.
+            let arg = if ty == VariantType::Tuple {
+                field.to_string()
+            } else {
+                field.trim_start_matches("r#").to_string()
+            };
+
+            // Keywords cannot be captured implicitly, and widths and precisions must be `usize`
+            // values rather than references, so those fields are passed by name.
+            let is_count = text[range.end..].starts_with('$');
+            if field.starts_with("r#") || is_count {
+                match named_fields.iter_mut().find(|(prev, ..)| *prev == arg) {
+                    Some((_, _, deref)) => *deref |= is_count,
+                    None => named_fields.push((arg.clone(), field.clone(), is_count)),
                 }
             }
 
-            display = display
-                .replace(&format!("{{{field}:"), "{:")
-                .replace(&format!("{{{field}}}"), "{}");
+            if arg != name {
+                renames.push((range, arg));
+            }
+            if !display_fields.contains(&field) {
+                display_fields.push(field);
+            }
+        }
+        for (arg, field, deref) in named_fields {
+            let mut tokens = vec![
+                TokenTree::Ident(Ident::new(&arg, display_span)),
+                TokenTree::Punct(Punct::new('=', Spacing::Alone)),
+            ];
+            if deref {
+                tokens.push(TokenTree::Punct(Punct::new('*', Spacing::Alone)));
+            }
+            tokens.push(TokenTree::Ident(match field.strip_prefix("r#") {
+                Some(raw) => Ident::new_raw(raw, display_span),
+                None => Ident::new(&field, display_span),
+            }));
+            display_args.push(tokens.into_iter().collect());
+        }
+
+        // Rename field references to their bindings.
+        if let Some(display) = &mut display {
+            for (range, field) in renames.into_iter().rev() {
+                display.replace_range(range, &field);
+            }
         }
 
+        // Bind the fields referenced by format arguments.
+        let display_args = display_args
+            .into_iter()
+            .map(|arg| bind_fields(arg, &fields, &mut display_fields).to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let deprecated = attrs
+            .iter()
+            .any(|attr| attr.name.to_string() == "deprecated");
+
         Ok(Self {
-            name,
+            name: name.to_string(),
             ty,
             fields,
+            field_spans,
             display,
             display_fields,
+            display_args,
+            display_macro,
             source,
+            from_span,
+            deref_source,
+            optional_source,
+            boxed_from,
+            from_with,
+            transparent,
+            forward_source,
+            skip_display,
+            fmt,
+            field_order,
+            backtrace,
+            provide,
+            code,
+            deprecated,
+        })
+    }
+}
+
+/// Find the argument names referenced by placeholders in a format string.
+///
+/// Returns the byte range of each name. Positional placeholders like `{}` do not reference any
+/// names and are skipped, as are escaped braces like `{{name}}`. Dynamic widths and precisions in
+/// the format spec, like `{value:>width$.prec$}`, are references too.
+fn format_references(display: &str) -> Vec<Range<usize>> {
+    let mut references = vec![];
+    let mut chars = display.char_indices().peekable();
+
+    while let Some((_, ch)) = chars.next() {
+        if ch != '{' || chars.next_if(|&(_, ch)| ch == '{').is_some() {
+            continue;
+        }
+
+        let start = chars.peek().map_or(display.len(), |&(i, _)| i);
+        let mut end = start;
+        while let Some((i, ch)) = chars.next_if(|&(_, ch)| ch != ':' && ch != '}') {
+            end = i + ch.len_utf8();
+        }
+
+        if start != end {
+            references.push(start..end);
+        }
+
+        // Names ending with `$` in the format spec are arguments too.
+        let mut name_start = None;
+        let mut after_dot = false;
+        for (i, ch) in chars.by_ref() {
+            match ch {
+                '}' => break,
+                '$' => {
+                    if let Some(mut name_start) = name_start.take() {
+                        // A leading `0` is the zero-padding flag before a width.
+                        if !after_dot && display[name_start..i].len() > 1 {
+                            name_start += usize::from(display[name_start..].starts_with('0'));
+                        }
+                        references.push(name_start..i);
+                    }
+                }
+                ch if ch == '_' || ch.is_alphanumeric() => {
+                    name_start.get_or_insert(i);
+                    continue;
+                }
+                _ => name_start = None,
+            }
+            after_dot = ch == '.';
+        }
+    }
+
+    references
+}
+
+/// Check that a placeholder name is either an integer index or an identifier.
+fn is_reference(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(ch) if ch.is_ascii_digit() => chars.all(|ch| ch.is_ascii_digit()),
+        Some(ch) if ch == '_' || ch.is_alphabetic() => {
+            chars.all(|ch| ch == '_' || ch.is_alphanumeric())
+        }
+        _ => false,
+    }
+}
+
+/// Reject a catch-all `From` conversion requested with `attr`.
+///
+/// Every type implementing `Error` would include the type itself.
+fn catch_all_error(attr: &str, span: Span) -> TokenStream {
+    let msg = format!(
+        "{attr} is not supported. A blanket `From<E: Error>` impl conflicts with `From<T> for T` \
+        because the type implements `Error`. Use `#[from] Box<dyn Error + Send + Sync>` and \
+        convert with `.map_err(Box::from)` instead"
+    );
+
+    spanned_error(msg, span)
+}
+
+/// Check that a `#[from(with = ...)]` closure takes the source and builds the variant `name`.
+///
+/// Closure bodies that do not start with a `Self::Variant` path are left to the type checker.
+fn check_from_with(name: &Ident, value: &TokenStream, span: Span) -> Result<(), TokenStream> {
+    let tokens = value.clone().into_iter().collect::<Vec<_>>();
+    let is_pipe =
+        |tree: &TokenTree| matches!(tree, TokenTree::Punct(punct) if punct.as_char() == '|');
+    let start = usize::from(
+        matches!(tokens.first(), Some(TokenTree::Ident(ident)) if ident.to_string() == "move"),
+    );
+
+    let end = tokens
+        .get(start)
+        .filter(|tree| is_pipe(tree))
+        .and_then(|_| tokens[start + 1..].iter().position(is_pipe))
+        .filter(|&len| len > 0);
+    let Some(len) = end else {
+        return Err(spanned_error(
+            format!(
+                "#[from(with = ...)] expects a closure that builds the variant from its source, \
+                like `|source| Self::{name} {{ ... }}`"
+            ),
+            tokens.first().map_or(span, TokenTree::span),
+        ));
+    };
+
+    let body = &tokens[start + len + 2..];
+    if let [TokenTree::Ident(ty), TokenTree::Punct(colons), _, TokenTree::Ident(variant), ..] = body
+    {
+        if ty.to_string() == "Self"
+            && colons.as_char() == ':'
+            && variant.to_string() != name.to_string()
+        {
+            let msg = format!(
+                "#[from(with = ...)] on variant `{name}` must build `Self::{name}`, not \
+                `Self::{variant}`"
+            );
+
+            return Err(spanned_error(msg, variant.span()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse attribute options like `#[error(...)]` in `name` or `name = value` form.
+///
+/// Attributes without arguments have no options.
+fn parse_options<'a>(
+    attrs: impl IntoIterator<Item = &'a Attribute>,
+) -> Result<Vec<(Ident, Option<TokenStream>)>, TokenStream> {
+    let mut options = vec![];
+
+    for attr in attrs {
+        let mut tree = attr.tree.clone();
+        if tree.peek().is_none() {
+            continue;
+        }
+        let group = tree.expect_group(Delimiter::Parenthesis)?;
+
+        for option in parse_format_args(group) {
+            let mut option = option.into_token_iter();
+            let name = option.try_ident()?;
+            let value = if option.peek().is_some() {
+                option.expect_punct('=')?;
+                Some(option.collect())
+            } else {
+                None
+            };
+
+            options.push((name, value));
+        }
+    }
+
+    Ok(options)
+}
+
+/// Split format arguments on top-level commas.
+///
+/// Commas inside turbofish generics like `::<A, B>` do not separate arguments.
+fn parse_format_args(input: TokenIter) -> Vec<TokenStream> {
+    let mut args = vec![];
+    let mut arg = vec![];
+    let mut turbofish = 0;
+    let mut colons = 0;
+
+    for tree in input {
+        if let TokenTree::Punct(punct) = &tree {
+            match punct.as_char() {
+                ',' if turbofish == 0 => {
+                    args.push(arg.drain(..).collect());
+                    continue;
+                }
+                '<' if colons == 2 || turbofish > 0 => turbofish += 1,
+                '>' if turbofish > 0 => turbofish -= 1,
+                _ => (),
+            }
+            colons = if punct.as_char() == ':' {
+                colons + 1
+            } else {
+                0
+            };
+        } else {
+            colons = 0;
+        }
+        arg.push(tree);
+    }
+    if !arg.is_empty() {
+        args.push(arg.into_iter().collect());
+    }
+
+    args
+}
+
+/// Find the fields referenced by a format argument.
+///
+/// Field names are bound directly, and `self.field` is rewritten to `(*field)` so it behaves like
+/// a field access on the variant. A leading `.field` or `.0` is shorthand for `self.field` or
+/// `self.0`.
+fn bind_fields(
+    arg: TokenStream,
+    fields: &HashMap<Rc<str>, String>,
+    bindings: &mut Vec<Rc<str>>,
+) -> TokenStream {
+    let mut output = vec![];
+    let mut input = arg.into_token_iter();
+    let mut after_dot = false;
+
+    if let Some(field) = parse_self_field(&mut input, fields) {
+        push_binding(bindings, field.to_string());
+        output.push(deref_field(field));
+    }
+
+    while let Some(tree) = input.next() {
+        let is_dot = matches!(&tree, TokenTree::Punct(punct) if punct.as_char() == '.');
+
+        match tree {
+            TokenTree::Group(group) => {
+                let stream = bind_fields(group.stream(), fields, bindings);
+                let mut inner = Group::new(group.delimiter(), stream);
+                inner.set_span(group.span());
+                output.push(TokenTree::Group(inner));
+            }
+            TokenTree::Ident(ident) if !after_dot && ident.to_string() == "self" => {
+                match parse_self_field(&mut input, fields) {
+                    Some(field) => {
+                        push_binding(bindings, field.to_string());
+                        output.push(deref_field(field));
+                    }
+                    None => output.push(TokenTree::Ident(ident)),
+                }
+            }
+            TokenTree::Ident(ident) => {
+                let name = ident.to_string();
+                if !after_dot && fields.contains_key(name.as_str()) {
+                    push_binding(bindings, name);
+                }
+                output.push(TokenTree::Ident(ident));
+            }
+            tree => output.push(tree),
+        }
+
+        after_dot = is_dot;
+    }
+
+    output.into_iter().collect()
+}
+
+/// Consume `.field` following `self` when it names a field of the variant.
+///
+/// Tuple fields like `self.0` are returned as their `field_0` binding.
+fn parse_self_field(input: &mut TokenIter, fields: &HashMap<Rc<str>, String>) -> Option<Ident> {
+    let mut ahead = input.clone();
+    ahead.expect_punct('.').ok()?;
+    let (name, field) = match ahead.next()? {
+        TokenTree::Ident(ident) => (ident.to_string(), ident),
+        TokenTree::Literal(lit) => {
+            let index = lit.to_string();
+            let field = Ident::new(&format!("field_{index}"), lit.span());
+            (index, field)
+        }
+        _ => return None,
+    };
+
+    // Method calls are left alone.
+    let is_call = matches!(
+        ahead.peek(),
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis
+    );
+
+    if is_call || !fields.contains_key(name.as_str()) {
+        return None;
+    }
+    *input = ahead;
+
+    Some(field)
+}
+
+/// Dereference a field binding as `(*field)`.
+fn deref_field(field: Ident) -> TokenTree {
+    let deref = [
+        TokenTree::Punct(Punct::new('*', Spacing::Alone)),
+        TokenTree::Ident(field),
+    ];
+
+    TokenTree::Group(Group::new(
+        Delimiter::Parenthesis,
+        deref.into_iter().collect(),
+    ))
+}
+
+fn push_binding(bindings: &mut Vec<Rc<str>>, name: String) {
+    let name = Rc::from(name);
+    if !bindings.contains(&name) {
+        bindings.push(name);
+    }
+}
+
+/// Check if an attribute has options in `name = value` form.
+fn is_option_attr(attr: &Attribute) -> bool {
+    let mut tree = attr.tree.clone();
+    let Ok(group) = tree.expect_group(Delimiter::Parenthesis) else {
+        return false;
+    };
+
+    parse_named_arg(&group.collect()).is_some()
+}
+
+/// Parse a visibility like `pub(crate)`, keeping its tokens.
+fn parse_type_visibility(input: &mut TokenIter) -> String {
+    let mut vis = vec![];
+    if let Some(tree) =
+        input.next_if(|tree| matches!(tree, TokenTree::Ident(ident) if ident.to_string() == "pub"))
+    {
+        vis.push(tree);
+        vis.extend(input.next_if(|tree| {
+            matches!(tree, TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis)
+        }));
+    }
+
+    vis.into_iter().collect::<TokenStream>().to_string()
+}
+
+/// Check if an attribute is a `#[derive(...)]` that includes `Debug`.
+fn is_debug_derive(attr: &Attribute) -> bool {
+    let mut tree = attr.tree.clone();
+    let Ok(group) = tree.expect_group(Delimiter::Parenthesis) else {
+        return false;
+    };
+
+    attr.name.to_string() == "derive"
+        && split_punct(group.collect(), ',').iter().any(|path| {
+            matches!(path.last(), Some(TokenTree::Ident(ident)) if ident.to_string() == "Debug")
         })
+}
+
+/// Check if an `#[error(...)]` attribute starts with a message.
+fn is_message_attr(attr: &Attribute) -> bool {
+    let mut tree = attr.tree.clone();
+    let Ok(mut group) = tree.expect_group(Delimiter::Parenthesis) else {
+        return false;
+    };
+
+    matches!(group.next(), Some(TokenTree::Literal(_)))
+}
+
+/// Get the lines of `#[doc = "..."]` attributes, which include doc comments.
+fn doc_comment(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.name.to_string() == "doc")
+        .filter_map(|attr| {
+            let mut tree = attr.tree.clone();
+            tree.expect_punct('=').ok()?;
+
+            tree.try_lit().and_then(|lit| parse_string(&lit)).ok()
+        })
+        .collect()
+}
+
+/// Get the value of a string literal, including raw strings.
+///
+/// All escapes are resolved before the message is scanned for placeholders, so the braces in
+/// `\u{1F600}` are never mistaken for one.
+fn parse_string(lit: &Literal) -> Result<String, TokenStream> {
+    let repr = lit.to_string();
+    let error = || spanned_error("Expected string literal", lit.span());
+
+    if let Some(raw) = repr.strip_prefix('r') {
+        let hashes = &raw[..raw.len() - raw.trim_start_matches('#').len()];
+        return raw
+            .strip_prefix(hashes)
+            .and_then(|raw| raw.strip_prefix('"'))
+            .and_then(|raw| raw.strip_suffix(hashes))
+            .and_then(|raw| raw.strip_suffix('"'))
+            .map(str::to_string)
+            .ok_or_else(error);
+    }
+
+    let inner = repr
+        .strip_prefix('"')
+        .and_then(|repr| repr.strip_suffix('"'))
+        .ok_or_else(error)?;
+    let mut string = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            string.push(ch);
+            continue;
+        }
+
+        let ch = match chars.next().ok_or_else(error)? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            ch @ ('\\' | '\'' | '"') => ch,
+            'x' => {
+                let code = chars.as_str().get(..2).ok_or_else(error)?;
+                chars.nth(1);
+
+                u8::from_str_radix(code, 16)
+                    .ok()
+                    .filter(u8::is_ascii)
+                    .ok_or_else(error)? as char
+            }
+            'u' => {
+                let rest = chars.as_str();
+                let end = rest.find('}').ok_or_else(error)?;
+                let code = rest[..end]
+                    .strip_prefix('{')
+                    .ok_or_else(error)?
+                    .replace('_', "");
+                chars = rest[end + 1..].chars();
+
+                u32::from_str_radix(&code, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(error)?
+            }
+            // A line continuation skips the newline and leading whitespace.
+            '\n' | '\r' => {
+                chars = chars.as_str().trim_start().chars();
+                continue;
+            }
+            _ => return Err(error()),
+        };
+        string.push(ch);
+    }
+
+    Ok(string)
+}
+
+/// Parse the default message on an enum, like `#[error("error in {variant}")]`.
+///
+/// The message is shared by variants with different fields, so it can only reference the variant
+/// name.
+fn parse_template(attr: &Attribute) -> Result<String, TokenStream> {
+    let mut tree = attr.tree.clone();
+    let mut tree = tree.expect_group(Delimiter::Parenthesis)?;
+    let lit = tree.try_lit()?;
+    let mut template = parse_string(&lit)?;
+
+    // Adjacent string literals are concatenated.
+    while matches!(tree.peek(), Some(TokenTree::Literal(_))) {
+        template.push_str(&parse_string(&tree.try_lit()?)?);
+    }
+    if let Some(tree) = tree.next() {
+        return Err(spanned_error(
+            "Format arguments are not allowed in the default message",
+            tree.span(),
+        ));
+    }
+
+    for range in format_references(&template) {
+        let name = &template[range];
+        if name != "variant" {
+            let msg = format!(
+                "Unknown reference `{name}` in default message. Only `{{variant}}` is allowed"
+            );
+
+            return Err(spanned_error(msg, lit.span()));
+        }
+    }
+
+    Ok(template.trim().to_string())
+}
+
+/// Get the inner type of an `Option<T>` type.
+pub(crate) fn option_type(ty: &str) -> Option<&str> {
+    let ty = ty.trim_start_matches("::");
+
+    ["Option<", "std::option::Option<", "core::option::Option<"]
+        .iter()
+        .find_map(|prefix| ty.strip_prefix(prefix))
+        .and_then(|inner| inner.strip_suffix('>'))
+}
+
+/// Get the inner type of a `Box<T>` type.
+fn boxed_type(ty: &str) -> Option<&str> {
+    let ty = ty.trim_start_matches("::");
+
+    ["Box<", "std::boxed::Box<", "alloc::boxed::Box<"]
+        .iter()
+        .find_map(|prefix| ty.strip_prefix(prefix))
+        .and_then(|inner| inner.strip_suffix('>'))
+}
+
+/// Parse an error code as a `u32` integer literal.
+fn parse_code(value: TokenStream) -> Result<u32, TokenStream> {
+    let msg = "Expected a `u32` integer literal";
+    let mut tree = value.into_token_iter();
+    let span = tree.peek().map_or_else(Span::call_site, TokenTree::span);
+    let (Ok(lit), None) = (tree.try_lit(), tree.next()) else {
+        return Err(spanned_error(msg, span));
+    };
+    let text = lit.to_string().replace('_', "");
+    let text = text.strip_suffix("u32").unwrap_or(&text);
+    let code = match text.get(..2) {
+        Some("0x") => u32::from_str_radix(&text[2..], 16),
+        Some("0o") => u32::from_str_radix(&text[2..], 8),
+        Some("0b") => u32::from_str_radix(&text[2..], 2),
+        _ => text.parse(),
+    };
+
+    code.map_err(|_| spanned_error(msg, lit.span()))
+}
+
+/// Get the name of a named format argument in `name = value` form.
+fn parse_named_arg(arg: &TokenStream) -> Option<String> {
+    let mut tree = arg.clone().into_token_iter();
+    let name = tree.try_ident().ok()?;
+
+    matches!(
+        tree.next(),
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '=' && punct.spacing() == Spacing::Alone
+    )
+    .then(|| name.to_string())
+}
+
+/// Parse a tuple field alias argument in `name = index` form.
+fn parse_alias(arg: &TokenStream) -> Option<(String, String)> {
+    let mut tree = arg.clone().into_token_iter();
+    let alias = tree.try_ident().ok()?;
+    tree.expect_punct('=').ok()?;
+    let index = tree.try_lit().ok()?.to_string();
+
+    (tree.peek().is_none() && index.bytes().all(|ch| ch.is_ascii_digit()))
+        .then(|| (alias.to_string(), index))
+}
+
+/// Split tokens on a punctuation character, ignoring any nested in `<...>`.
+fn split_punct(tokens: Vec<TokenTree>, ch: char) -> Vec<Vec<TokenTree>> {
+    let mut parts = vec![vec![]];
+    let mut depth = 0_usize;
+    let mut arrow = false;
+
+    for tree in tokens {
+        if let TokenTree::Punct(punct) = &tree {
+            match punct.as_char() {
+                c if c == ch && depth == 0 => {
+                    parts.push(vec![]);
+                    arrow = false;
+                    continue;
+                }
+                '<' => depth += 1,
+                '>' if !arrow => depth = depth.saturating_sub(1),
+                _ => (),
+            }
+            arrow = punct.as_char() == '-';
+        } else {
+            arrow = false;
+        }
+
+        if let Some(part) = parts.last_mut() {
+            part.push(tree);
+        }
     }
+
+    parts
 }
 
-fn parse_tuple_fields(input: TokenStream) -> Result<OrderedMap<Field>, TokenStream> {
+/// Parse the fields of the tuple variant (or tuple struct) `parent`.
+fn parse_tuple_fields(
+    input: TokenStream,
+    parent: &Ident,
+) -> Result<OrderedMap<Field>, TokenStream> {
     let mut input = input.into_token_iter();
     let mut fields = OrderedMap::new();
     let mut index = 0;
 
     while input.peek().is_some() {
-        let field = parse_tuple_field(&mut input)?;
+        let field = parse_tuple_field(&mut input, index, parent)?;
         fields.insert(index.to_string().into(), field);
         index += 1;
     }
@@ -210,38 +1875,154 @@ fn parse_tuple_fields(input: TokenStream) -> Result<OrderedMap<Field>, TokenStre
     Ok(fields)
 }
 
-fn parse_tuple_field(input: &mut TokenIter) -> Result<Field, TokenStream> {
+fn parse_tuple_field(
+    input: &mut TokenIter,
+    index: usize,
+    parent: &Ident,
+) -> Result<Field, TokenStream> {
     let attrs = input.parse_attributes()?;
-    let (path, _) = input.parse_path()?;
+    let span = input.peek().map_or(parent.span(), TokenTree::span);
+    parse_type_visibility(input);
+    let ty_span = input.peek().map_or(span, TokenTree::span);
+    let path = parse_type(input).map_err(|_| {
+        spanned_error(
+            format!("Expected a type for field `{index}` of `{parent}`"),
+            span,
+        )
+    })?;
     let _ = input.expect_punct(',');
 
-    Ok(Field { attrs, path })
+    Ok(Field {
+        attrs,
+        path,
+        span: ty_span,
+    })
 }
 
-fn parse_struct_fields(input: TokenStream) -> Result<OrderedMap<Field>, TokenStream> {
+/// Parse the fields of the struct variant (or struct) `parent`.
+fn parse_struct_fields(
+    input: TokenStream,
+    parent: &Ident,
+) -> Result<OrderedMap<Field>, TokenStream> {
     let mut input = input.into_token_iter();
     let mut fields = OrderedMap::new();
 
     while input.peek().is_some() {
-        let (name, field) = parse_struct_field(&mut input)?;
-        fields.insert(name.into(), field);
+        let (name, field) = parse_struct_field(&mut input, parent)?;
+
+        // The first field would be overwritten.
+        if fields.insert(name.to_string().into(), field).is_some() {
+            let msg = format!("Duplicate field `{name}` in `{parent}`");
+
+            return Err(spanned_error(msg, name.span()));
+        }
     }
 
     Ok(fields)
 }
 
-fn parse_struct_field(input: &mut TokenIter) -> Result<(String, Field), TokenStream> {
+fn parse_struct_field(
+    input: &mut TokenIter,
+    parent: &Ident,
+) -> Result<(Ident, Field), TokenStream> {
     let attrs = input.parse_attributes()?;
-    let name = input.try_ident()?;
-    input.expect_punct(':')?;
-    let (path, _) = input.parse_path()?;
+    parse_type_visibility(input);
+    let span = input.peek().map_or(parent.span(), TokenTree::span);
+    let name = input
+        .try_ident()
+        .map_err(|_| spanned_error(format!("Expected a field name in `{parent}`"), span))?;
+    if input.expect_punct(':').is_err() {
+        let msg = format!("Expected `:` after field `{name}` of `{parent}`");
+
+        return Err(spanned_error(msg, name.span()));
+    }
+    let ty_span = input.peek().map_or(name.span(), TokenTree::span);
+    let path = parse_type(input).map_err(|_| {
+        spanned_error(
+            format!("Expected a type for field `{name}` of `{parent}`"),
+            name.span(),
+        )
+    })?;
     let _ = input.expect_punct(',');
 
-    Ok((name.to_string(), Field { attrs, path }))
+    Ok((
+        name,
+        Field {
+            attrs,
+            path,
+            span: ty_span,
+        },
+    ))
+}
+
+/// Parse a field type up to the next top-level `,`.
+///
+/// This accepts types that `parse_path` cannot, like `dyn Error + 'static` and arrays with const
+/// generic lengths.
+fn parse_type(input: &mut TokenIter) -> Result<String, TokenStream> {
+    let mut tokens = vec![];
+    let mut depth = 0_usize;
+    let mut arrow = false;
+
+    while let Some(tree) = input.next_if(
+        |tree| !matches!(tree, TokenTree::Punct(punct) if punct.as_char() == ',' && depth == 0),
+    ) {
+        if let TokenTree::Punct(punct) = &tree {
+            match punct.as_char() {
+                '<' => depth += 1,
+                '>' if !arrow => depth = depth.saturating_sub(1),
+                _ => (),
+            }
+            arrow = punct.as_char() == '-';
+        } else {
+            arrow = false;
+        }
+        tokens.push(tree);
+    }
+
+    if tokens.is_empty() {
+        return Err(spanned_error("Expected type", input.next().as_span()));
+    }
+
+    Ok(type_to_string(tokens))
+}
+
+/// Stringify type tokens compactly, as in `Box<dyn Error + 'static>` -> `Box<dyn Error+'static>`.
+fn type_to_string(tokens: impl IntoIterator<Item = TokenTree>) -> String {
+    let mut string = String::new();
+    let mut word = false;
+
+    for tree in tokens {
+        match tree {
+            TokenTree::Ident(_) | TokenTree::Literal(_) => {
+                if word {
+                    string.push(' ');
+                }
+                string.push_str(&tree.to_string());
+                word = true;
+                continue;
+            }
+            TokenTree::Punct(punct) => string.push(punct.as_char()),
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::None => ("", ""),
+                };
+                string.push_str(open);
+                string.push_str(&type_to_string(group.stream()));
+                string.push_str(close);
+            }
+        }
+        word = false;
+    }
+
+    string
 }
 
 impl ErrorSource {
-    fn as_ref(&self) -> Option<&Rc<str>> {
+    pub(crate) fn as_ref(&self) -> Option<&Rc<str>> {
         match self {
             Self::None => None,
             Self::From(name) | Self::Source(name) => Some(name),
@@ -257,10 +2038,6 @@ impl<T> OrderedMap<T> {
         }
     }
 
-    fn len(&self) -> usize {
-        self.keys.len()
-    }
-
     fn into_iter(mut self) -> impl Iterator<Item = (Rc<str>, T)> {
         self.keys.into_iter().map(move |key| {
             let value = self.map.remove(&key).unwrap();
@@ -279,3 +2056,175 @@ impl<T> OrderedMap<T> {
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Extract the references from a format string by following the `std::fmt` grammar.
+    ///
+    /// This is a deliberately naive parser that serves as the reference for the scanner. It only
+    /// has to handle valid format strings.
+    fn spec_references(display: &str) -> Vec<&str> {
+        let mut references = vec![];
+        let mut rest = display;
+
+        while let Some(start) = rest.find(['{', '}']) {
+            if rest[start + 1..].starts_with(&rest[start..=start]) {
+                rest = &rest[start + 2..];
+                continue;
+            }
+
+            let (format, tail) = rest[start + 1..].split_once('}').unwrap();
+            rest = tail;
+
+            let (argument, mut spec) = format.split_once(':').unwrap_or((format, ""));
+            if !argument.is_empty() {
+                references.push(argument);
+            }
+
+            // format_spec := [[fill]align][sign]['#']['0'][width]['.' precision]type
+            let mut chars = spec.chars();
+            match (chars.next(), chars.next()) {
+                (Some(fill), Some('<' | '^' | '>')) => spec = &spec[fill.len_utf8() + 1..],
+                (Some('<' | '^' | '>'), _) => spec = &spec[1..],
+                _ => (),
+            }
+            spec = spec.strip_prefix(['+', '-']).unwrap_or(spec);
+            spec = spec.strip_prefix('#').unwrap_or(spec);
+            if !spec.starts_with("0$") {
+                spec = spec.strip_prefix('0').unwrap_or(spec);
+            }
+
+            let (width, tail) = spec_count(spec);
+            references.extend(width);
+            if let Some(tail) = tail.strip_prefix('.') {
+                if !tail.starts_with('*') {
+                    references.extend(spec_count(tail).0);
+                }
+            }
+        }
+
+        references
+    }
+
+    /// Split a width or precision from the front of a format spec, with the argument it names.
+    fn spec_count(spec: &str) -> (Option<&str>, &str) {
+        let end = spec
+            .find(|ch: char| ch != '_' && !ch.is_alphanumeric())
+            .unwrap_or(spec.len());
+
+        match spec[end..].strip_prefix('$') {
+            Some(tail) if end > 0 => (Some(&spec[..end]), tail),
+            _ => {
+                let end = spec
+                    .find(|ch: char| !ch.is_ascii_digit())
+                    .unwrap_or(spec.len());
+
+                (None, &spec[end..])
+            }
+        }
+    }
+
+    /// Generate a valid format string.
+    ///
+    /// Braces only appear in literal text as `{{` and `}}` escapes, which are mixed with non-ASCII
+    /// text. Widths and precisions may be taken from arguments with `$` and `*`.
+    fn format_string() -> impl Strategy<Value = String> {
+        let text = "([a-zA-Z0-9 .,:;?!#$%'\"()\\[\\]<>=+*/-]|é|ß|日本|🦀|\\{\\{|\\}\\}){0,8}";
+        let name = || {
+            prop_oneof![
+                "[0-9]{1,2}",
+                "[a-zA-Z_][a-zA-Z0-9_]{0,8}",
+                "(é|[a-zA-Z_])(ö|[a-zA-Z0-9_]){0,4}",
+            ]
+        };
+        let count = || {
+            prop_oneof![
+                Just(String::new()),
+                "[0-9]{1,2}",
+                name().prop_map(|name| format!("{name}$")),
+            ]
+        };
+        let precision = prop_oneof![
+            Just(String::new()),
+            Just(".*".to_string()),
+            count().prop_filter_map("precision", |count| {
+                Some(format!(".{count}")).filter(|_| !count.is_empty())
+            }),
+        ];
+        let spec = (
+            "(([a-z0-9*$ é]|日)?[<>^])?[+-]?#?0?",
+            count(),
+            precision,
+            "|\\?|x\\?|#?x|X|e",
+        )
+            .prop_map(|(flags, width, precision, ty)| format!(":{flags}{width}{precision}{ty}"));
+        let spec = prop_oneof![Just(String::new()), spec];
+        let name = prop_oneof![Just(String::new()), name()];
+
+        prop::collection::vec((text, name, spec), 0..8).prop_map(|parts| {
+            let mut display = String::new();
+            for (text, name, spec) in parts {
+                display.push_str(&text);
+                display.push('{');
+                display.push_str(&name);
+                display.push_str(&spec);
+                display.push('}');
+            }
+
+            display
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_format_references(display in format_string()) {
+            let references = format_references(&display)
+                .into_iter()
+                .map(|range| &display[range])
+                .collect::<Vec<_>>();
+
+            prop_assert_eq!(references, spec_references(&display));
+        }
+    }
+
+    #[test]
+    fn test_format_spec_references() {
+        let display = "{value:>width$.prec$} {:1$} {:$>5} {0:.*} {:0width$} {:.0$} {:02$}";
+        let references = format_references(display)
+            .into_iter()
+            .map(|range| &display[range])
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            references,
+            ["value", "width", "prec", "1", "0", "width", "0", "2"]
+        );
+        assert_eq!(spec_references(display), references);
+    }
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("Io"), "io");
+        assert_eq!(snake_case("NotFound"), "not_found");
+        assert_eq!(snake_case("IOError"), "io_error");
+        assert_eq!(snake_case("Http2Error"), "http2_error");
+        assert_eq!(snake_case("Invalid_Input"), "invalid_input");
+        assert_eq!(snake_case("r#Type"), "type");
+    }
+
+    #[test]
+    fn test_rename() {
+        let renamed = RENAME_RULES.map(|rule| rename("IOError", rule));
+        assert_eq!(
+            renamed,
+            [
+                "ioerror", "IOERROR", "IOError", "iOError", "io_error", "IO_ERROR", "io-error",
+                "IO-ERROR",
+            ],
+        );
+        assert_eq!(rename("r#Type", "camelCase"), "type");
+    }
+}